@@ -0,0 +1,390 @@
+use crate::cards::{CardAction, ChanceCardEnum, CommunityChestCardEnum, NEAREST_RAILROAD_MULTIPLIER, NEAREST_UTILITY_MULTIPLIER};
+use crate::location::{LocationEnum, LocationTypeEnum, Properties};
+use crate::player::JAIL_ROLL_ATTEMPTS;
+
+const SQUARES: usize = 40;
+const MAX_ITERATIONS: usize = 10_000;
+const TOLERANCE: f64 = 1e-13;
+
+/// What a player in jail does at the start of each turn.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum JailPolicyEnum {
+    /// Pay the fine straight away and roll as normal, doubles earning another roll.
+    LeaveEarly,
+    /// Roll for doubles until the last attempt forces the fine.
+    StayInJail,
+}
+
+/// Where a roll can leave a player once cards and Go To Jail are resolved.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+enum Outcome {
+    Square(LocationEnum),
+    /// Sent by an "advance to nearest" card, which charges more than the usual rent.
+    NearestByCard(LocationEnum),
+    Jail,
+}
+
+/// A square landed on, and whether an "advance to nearest" card sent the player there.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct Arrival {
+    location: LocationEnum,
+    by_card: bool,
+}
+
+/// The state a roll leads to, the square it lands on if any, and its probability.
+type Transition = (usize, Option<Arrival>, f64);
+
+/// Long-run landing frequencies for every square, treating the game as a Markov chain over
+/// where the player stands, how many doubles they have rolled this turn and how long they
+/// have been in jail. Card decks are modelled as a fresh random draw every time.
+#[derive(PartialEq, Debug, Clone)]
+pub struct LandingAnalysis {
+    pub jail_policy: JailPolicyEnum,
+    /// The chance that a given move ends on each square, indexed from Go. Sums to 1.
+    pub probabilities: Vec<f64>,
+    /// How often each square is landed on per turn, counting extra rolls after doubles.
+    /// Jail counts both visiting and being sent there.
+    pub landings_per_turn: Vec<f64>,
+    /// The part of `landings_per_turn` that comes from "advance to nearest" cards.
+    pub card_landings_per_turn: Vec<f64>,
+}
+
+/// Rent a deed earns at one build level.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RentLevel {
+    /// "Deed", "Set", "1 house" to "4 houses" and "Hotel" for streets, "1 railroad" to
+    /// "4 railroads" and "1 utility" or "2 utilities" for the rest.
+    pub label: String,
+    /// Rent for a plain landing. Utilities assume the average roll of 7.
+    pub rent: f64,
+    /// Rent collected per opponent turn. Landings from "advance to nearest" cards count at the
+    /// card's rent: twice the railroad rent, or ten times a fresh roll on a utility.
+    pub expected_rent: f64,
+    /// The deed's price plus every house or hotel needed to reach this level.
+    pub investment: i32,
+    /// Opponent turns until the rent collected covers `investment`.
+    pub break_even_turns: f64,
+}
+
+impl LandingAnalysis {
+    pub fn new(jail_policy: JailPolicyEnum) -> Self {
+        let transitions = build_transitions(jail_policy);
+        let distribution = find_stationary_distribution(&transitions);
+
+        let mut landings_per_roll = vec![0.0; SQUARES];
+        let mut card_landings_per_roll = vec![0.0; SQUARES];
+        let mut turns_started = 0.0;
+        for (state, weight) in distribution.iter().enumerate() {
+            if starts_turn(state) {
+                turns_started += weight;
+            }
+            for (_, arrival, probability) in &transitions[state] {
+                if let Some(arrival) = arrival {
+                    let index = arrival.location as usize - 1;
+                    landings_per_roll[index] += weight * probability;
+                    if arrival.by_card {
+                        card_landings_per_roll[index] += weight * probability;
+                    }
+                }
+            }
+        }
+
+        let total: f64 = landings_per_roll.iter().sum();
+        LandingAnalysis {
+            jail_policy,
+            probabilities: landings_per_roll.iter().map(|landings| landings / total).collect(),
+            landings_per_turn: landings_per_roll.iter().map(|landings| landings / turns_started).collect(),
+            card_landings_per_turn: card_landings_per_roll.iter().map(|landings| landings / turns_started).collect(),
+        }
+    }
+
+    pub fn get_probability(&self, location: LocationEnum) -> f64 {
+        self.probabilities[location as usize - 1]
+    }
+
+    pub fn get_landings_per_turn(&self, location: LocationEnum) -> f64 {
+        self.landings_per_turn[location as usize - 1]
+    }
+
+    pub fn get_card_landings_per_turn(&self, location: LocationEnum) -> f64 {
+        self.card_landings_per_turn[location as usize - 1]
+    }
+
+    /// Expected rent and break-even point at each build level of the deed on `location`.
+    /// Returns nothing for squares that cannot be bought.
+    pub fn get_rent_levels(&self, properties: &Properties, location: LocationEnum) -> Vec<RentLevel> {
+        let property = properties.get_property_ref(location);
+        let card_landings = self.get_card_landings_per_turn(location);
+        let landings = self.get_landings_per_turn(location) - card_landings;
+        let level = |label: String, rent: f64, card_rent: f64, investment: i32| {
+            let expected_rent = rent * landings + card_rent * card_landings;
+            let break_even_turns = if expected_rent > 0.0 { investment as f64 / expected_rent } else { f64::INFINITY };
+            RentLevel { label, rent, expected_rent, investment, break_even_turns }
+        };
+
+        match property.location_type {
+            LocationTypeEnum::Property => {
+                let mut levels = vec![
+                    level("Deed".to_string(), property.rent[0] as f64, 0.0, property.cost),
+                    level("Set".to_string(), (property.rent[0] * 2) as f64, 0.0, property.cost),
+                ];
+                for houses in 1..property.rent.len() {
+                    let label = match houses {
+                        1 => "1 house".to_string(),
+                        5 => "Hotel".to_string(),
+                        _ => format!("{} houses", houses),
+                    };
+                    levels.push(level(label, property.rent[houses] as f64, 0.0, property.cost + property.house_cost * houses as i32));
+                }
+                levels
+            },
+            LocationTypeEnum::Railroad => property.rent.iter().enumerate()
+                .map(|(owned, rent)| {
+                    let label = if owned == 0 { "1 railroad".to_string() } else { format!("{} railroads", owned + 1) };
+                    level(label, *rent as f64, (*rent * NEAREST_RAILROAD_MULTIPLIER) as f64, property.cost)
+                })
+                .collect(),
+            // Utility rent multiplies the roll that landed there, which averages 7.
+            LocationTypeEnum::Utility => property.rent.iter().enumerate()
+                .map(|(owned, multiplier)| {
+                    let label = if owned == 0 { "1 utility".to_string() } else { format!("{} utilities", owned + 1) };
+                    level(label, (*multiplier * 7) as f64, (NEAREST_UTILITY_MULTIPLIER * 7) as f64, property.cost)
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// One row per square and build level, ready to paste into a spreadsheet.
+    pub fn to_csv(&self, properties: &Properties) -> String {
+        let mut csv = String::from("square,probability,landings_per_turn,level,rent,expected_rent,investment,break_even_turns\n");
+        for number in 1..=SQUARES {
+            let location = LocationEnum::try_from(number).expect("Every square from 1 to 40 exists!");
+            let name = location.get_name();
+            let (probability, landings) = (self.get_probability(location), self.get_landings_per_turn(location));
+            let levels = self.get_rent_levels(properties, location);
+            if levels.is_empty() {
+                csv.push_str(&format!("{},{:.5},{:.5},,,,,\n", name, probability, landings));
+            }
+            for level in levels {
+                csv.push_str(&format!("{},{:.5},{:.5},{},{},{:.3},{},{:.1}\n",
+                    name, probability, landings, level.label, level.rent, level.expected_rent, level.investment, level.break_even_turns));
+            }
+        }
+        csv
+    }
+}
+
+// States 0-119 are (square, doubles this turn) for every square but Go To Jail, where nobody
+// stays, and states 120 onwards count failed attempts to roll out of jail.
+const FREE_STATES: usize = SQUARES * 3;
+const JAIL_STATES: usize = JAIL_ROLL_ATTEMPTS as usize;
+
+fn free_state(location: LocationEnum, doubles: usize) -> usize {
+    (location as usize - 1) * 3 + doubles
+}
+
+fn starts_turn(state: usize) -> bool {
+    state >= FREE_STATES || state % 3 == 0
+}
+
+/// For every state, each (next state, square landed on, probability) that one roll can lead to.
+/// A failed roll to leave jail moves nobody, so it lands nowhere.
+fn build_transitions(jail_policy: JailPolicyEnum) -> Vec<Vec<Transition>> {
+    let mut transitions = vec![Vec::new(); FREE_STATES + JAIL_STATES];
+    for number in 1..=SQUARES {
+        let location = LocationEnum::try_from(number).expect("Every square from 1 to 40 exists!");
+        if location == LocationEnum::GoToJail {
+            continue;
+        }
+        for doubles in 0..3 {
+            transitions[free_state(location, doubles)] = roll_from(location, doubles);
+        }
+    }
+
+    for attempts in 0..JAIL_STATES {
+        let state = FREE_STATES + attempts;
+        if jail_policy == JailPolicyEnum::LeaveEarly {
+            transitions[state] = roll_from(LocationEnum::Jail, 0);
+            continue;
+        }
+        for (first, second, probability) in get_roll_odds() {
+            if first == second || attempts + 1 == JAIL_STATES {
+                // Leaving jail ends the turn, even on doubles.
+                add_move(&mut transitions[state], LocationEnum::Jail, first + second, 0, false, probability);
+            } else {
+                transitions[state].push((state + 1, None, probability));
+            }
+        }
+    }
+    transitions
+}
+
+fn roll_from(location: LocationEnum, doubles: usize) -> Vec<Transition> {
+    let mut moves = Vec::new();
+    for (first, second, probability) in get_roll_odds() {
+        if first == second && doubles == 2 {
+            moves.push((FREE_STATES, Some(Arrival { location: LocationEnum::Jail, by_card: false }), probability));
+        } else {
+            add_move(&mut moves, location, first + second, doubles, first == second, probability);
+        }
+    }
+    moves
+}
+
+fn add_move(moves: &mut Vec<Transition>,
+            from: LocationEnum,
+            spaces: i32,
+            doubles: usize,
+            rolls_again: bool,
+            probability: f64) {
+    for (outcome, chance) in resolve(from.advance(spaces)) {
+        let (location, by_card) = match outcome {
+            Outcome::Jail => {
+                moves.push((FREE_STATES, Some(Arrival { location: LocationEnum::Jail, by_card: false }), probability * chance));
+                continue;
+            },
+            Outcome::Square(square) => (square, false),
+            Outcome::NearestByCard(square) => (square, true),
+        };
+        let next = if rolls_again { free_state(location, doubles + 1) } else { free_state(location, 0) };
+        moves.push((next, Some(Arrival { location, by_card }), probability * chance));
+    }
+}
+
+/// Where landing on `location` finally leaves the player, following any card drawn there.
+fn resolve(location: LocationEnum) -> Vec<(Outcome, f64)> {
+    let actions: Vec<CardAction> = match location.get_location_type() {
+        LocationTypeEnum::Chance => ChanceCardEnum::get_deck().iter().map(ChanceCardEnum::get_action).collect(),
+        LocationTypeEnum::CommunityChest => CommunityChestCardEnum::get_deck().iter().map(CommunityChestCardEnum::get_action).collect(),
+        _ if location == LocationEnum::GoToJail => return vec![(Outcome::Jail, 1.0)],
+        _ => return vec![(Outcome::Square(location), 1.0)],
+    };
+
+    let chance = 1.0 / actions.len() as f64;
+    let mut outcomes = Vec::new();
+    for action in actions {
+        match action {
+            CardAction::AdvanceTo(target) => outcomes.push((Outcome::Square(target), chance)),
+            CardAction::AdvanceToNearest(location_type) => {
+                let nearest = (1..=SQUARES as i32)
+                    .map(|offset| location.advance(offset))
+                    .find(|square| square.get_location_type() == location_type)
+                    .expect("Every board has railroads and utilities!");
+                outcomes.push((Outcome::NearestByCard(nearest), chance));
+            },
+            CardAction::GoBack(spaces) => {
+                for (outcome, odds) in resolve(location.retreat(spaces)) {
+                    outcomes.push((outcome, chance * odds));
+                }
+            },
+            CardAction::GoToJail => outcomes.push((Outcome::Jail, chance)),
+            _ => outcomes.push((Outcome::Square(location), chance)),
+        }
+    }
+    outcomes
+}
+
+fn get_roll_odds() -> Vec<(i32, i32, f64)> {
+    let mut odds = Vec::new();
+    for first in 1..=6 {
+        for second in 1..=6 {
+            odds.push((first, second, 1.0 / 36.0));
+        }
+    }
+    odds
+}
+
+fn find_stationary_distribution(transitions: &[Vec<Transition>]) -> Vec<f64> {
+    let mut distribution = vec![0.0; transitions.len()];
+    distribution[free_state(LocationEnum::Go, 0)] = 1.0;
+    for _ in 0..MAX_ITERATIONS {
+        let mut next = vec![0.0; transitions.len()];
+        for (state, moves) in transitions.iter().enumerate() {
+            for (to, _, probability) in moves {
+                next[*to] += distribution[state] * probability;
+            }
+        }
+        // Averaging with the last step damps the oscillation of a chain that cycles round the board.
+        for (value, previous) in next.iter_mut().zip(&distribution) {
+            *value = (*value + previous) / 2.0;
+        }
+        let change: f64 = next.iter().zip(&distribution).map(|(a, b)| (a - b).abs()).sum();
+        distribution = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    distribution
+}
+
+#[cfg(test)]
+mod test {
+    use crate::analysis::{JailPolicyEnum, LandingAnalysis};
+    use crate::location::{LocationEnum, Properties};
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 0.0005, "expected about {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn probabilities_match_published_figures() {
+        let analysis = LandingAnalysis::new(JailPolicyEnum::StayInJail);
+        assert_near(analysis.probabilities.iter().sum(), 1.0);
+        assert_eq!(analysis.get_probability(LocationEnum::GoToJail), 0.0);
+        assert_near(analysis.get_probability(LocationEnum::Jail), 0.0621);
+        assert_near(analysis.get_probability(LocationEnum::IllinoisAvenue), 0.0318);
+        assert_near(analysis.get_probability(LocationEnum::ParkPlace), 0.0219);
+        assert_near(analysis.get_probability(LocationEnum::Chance3), 0.0087);
+
+        let most_landed = (1..=40)
+            .filter(|number| *number != LocationEnum::Jail as usize)
+            .max_by(|a, b| analysis.probabilities[a - 1].total_cmp(&analysis.probabilities[b - 1]))
+            .unwrap();
+        assert_eq!(most_landed, LocationEnum::IllinoisAvenue as usize);
+    }
+
+    #[test]
+    fn staying_in_jail_favours_the_squares_just_past_it() {
+        let staying = LandingAnalysis::new(JailPolicyEnum::StayInJail);
+        let leaving = LandingAnalysis::new(JailPolicyEnum::LeaveEarly);
+        assert!(staying.get_probability(LocationEnum::StJamesPlace) > leaving.get_probability(LocationEnum::StJamesPlace));
+        assert!(staying.get_probability(LocationEnum::TennesseeAvenue) > leaving.get_probability(LocationEnum::TennesseeAvenue));
+        assert!(leaving.get_probability(LocationEnum::NewYorkAvenue) > staying.get_probability(LocationEnum::NewYorkAvenue));
+        // Doubles and paying out of jail mean more than one move a turn on average.
+        let moves_per_turn = |analysis: &LandingAnalysis| analysis.landings_per_turn.iter().sum::<f64>();
+        assert!(moves_per_turn(&leaving) > moves_per_turn(&staying));
+        assert!(moves_per_turn(&staying) > 1.0);
+    }
+
+    #[test]
+    fn rent_levels_show_expected_rent_and_break_even() {
+        let analysis = LandingAnalysis::new(JailPolicyEnum::StayInJail);
+        let properties = Properties::get_new_list();
+        let levels = analysis.get_rent_levels(&properties, LocationEnum::Boardwalk);
+        let labels: Vec<&str> = levels.iter().map(|level| level.label.as_str()).collect();
+        assert_eq!(labels, vec!["Deed", "Set", "1 house", "2 houses", "3 houses", "4 houses", "Hotel"]);
+        let hotel = &levels[6];
+        assert_eq!(hotel.investment, 400 + 5 * 200);
+        assert_near(hotel.expected_rent, 2000.0 * analysis.get_landings_per_turn(LocationEnum::Boardwalk));
+        assert_near(hotel.break_even_turns * hotel.expected_rent, 1400.0);
+        assert!(levels[4].break_even_turns < levels[1].break_even_turns);
+
+        assert_eq!(analysis.get_rent_levels(&properties, LocationEnum::ShortLine).len(), 4);
+        assert_eq!(analysis.get_rent_levels(&properties, LocationEnum::WaterWorks)[1].rent, 70.0);
+        assert!(analysis.get_rent_levels(&properties, LocationEnum::LuxuryTax).is_empty());
+
+        // Part of every utility's landings come from the card, which always charges ten times the roll.
+        let card_landings = analysis.get_card_landings_per_turn(LocationEnum::ElectricCompany);
+        assert!(card_landings > 0.0);
+        assert_eq!(analysis.get_card_landings_per_turn(LocationEnum::Boardwalk), 0.0);
+        let one_utility = &analysis.get_rent_levels(&properties, LocationEnum::ElectricCompany)[0];
+        let landings = analysis.get_landings_per_turn(LocationEnum::ElectricCompany);
+        assert_near(one_utility.expected_rent, 28.0 * (landings - card_landings) + 70.0 * card_landings);
+        assert!(one_utility.expected_rent > 28.0 * landings);
+
+        let csv = analysis.to_csv(&properties);
+        assert_eq!(csv.lines().count(), 1 + 22 * 7 + 4 * 4 + 2 * 2 + 12);
+        assert!(csv.lines().any(|line| line.starts_with("Boardwalk,") && line.contains(",Hotel,2000,")));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::board_state::BoardState;
+use crate::events::GameEvent;
+use crate::location::LocationEnum;
+use crate::Player;
+
+pub const MINIMUM_BID_INCREMENT: i32 = 10;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum AuctionStyleEnum {
+    /// Every player bids once without seeing the others.
+    Sealed,
+    /// Players keep raising the high bid until only one is left.
+    OpenAscending,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct AuctionResult {
+    pub location: LocationEnum,
+    pub winner: usize,
+    pub price: i32,
+}
+
+pub trait AuctionBidder {
+    /// The bid `player_number` makes for `location` while `highest_bid` stands, or None to drop out.
+    /// Sealed auctions always pass a `highest_bid` of 0.
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32>;
+}
+
+impl BoardState {
+    /// Auctions an unowned deed to every solvent player, starting with whoever's turn it is.
+    pub fn auction_property(&mut self, location: LocationEnum, style: AuctionStyleEnum, bidder: &mut dyn AuctionBidder) -> Option<AuctionResult> {
+        if self.properties.get_property_ref(location).owned_by_player_number.is_some() {
+            return None;
+        }
+
+        let bidders = self.get_auction_order();
+        let (winner, price) = match style {
+            AuctionStyleEnum::Sealed => self.collect_sealed_bids(location, &bidders, bidder)?,
+            AuctionStyleEnum::OpenAscending => self.collect_open_bids(location, &bidders, bidder)?,
+        };
+
+        self.get_current_player_by_number(winner).cash -= price;
+        self.properties.get_property(location).owned_by_player_number = Some(winner);
+        self.emit(GameEvent::AuctionWon { player_number: winner, location, price });
+        Some(AuctionResult { location, winner, price })
+    }
+
+    fn collect_sealed_bids(&self, location: LocationEnum, bidders: &[usize], bidder: &mut dyn AuctionBidder) -> Option<(usize, i32)> {
+        let mut best: Option<(usize, i32)> = None;
+        for player_number in bidders {
+            let player = &self.players[player_number];
+            let bid = match bidder.bid(self, *player_number, location, 0) {
+                Some(bid) if self.is_valid_bid(player, bid, 0) => bid,
+                _ => continue,
+            };
+            // Ties go to the earlier bidder.
+            if best.map_or(true, |(_, best_bid)| bid > best_bid) {
+                best = Some((*player_number, bid));
+            }
+        }
+        best
+    }
+
+    fn collect_open_bids(&self, location: LocationEnum, bidders: &[usize], bidder: &mut dyn AuctionBidder) -> Option<(usize, i32)> {
+        let mut active = bidders.to_vec();
+        let mut best: Option<(usize, i32)> = None;
+        loop {
+            let mut raised = false;
+            for player_number in active.clone() {
+                let highest_bid = best.map_or(0, |(_, bid)| bid);
+                if best.is_some_and(|(leader, _)| leader == player_number) {
+                    continue;
+                }
+
+                let player = &self.players[&player_number];
+                match bidder.bid(self, player_number, location, highest_bid) {
+                    Some(bid) if self.is_valid_bid(player, bid, highest_bid) => {
+                        best = Some((player_number, bid));
+                        raised = true;
+                    },
+                    _ => active.retain(|other| *other != player_number),
+                }
+            }
+
+            if !raised {
+                return best;
+            }
+        }
+    }
+
+    fn is_valid_bid(&self, player: &Player, bid: i32, highest_bid: i32) -> bool {
+        bid >= highest_bid + MINIMUM_BID_INCREMENT && bid <= player.cash
+    }
+
+    fn get_auction_order(&self) -> Vec<usize> {
+        let count = self.players.len();
+        (0..count)
+            .map(|offset| (self.player_turn - 1 + offset) % count + 1)
+            .filter(|player_number| !self.players[player_number].is_bankrupt)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use crate::BoardState;
+    use crate::auction::{AuctionBidder, AuctionResult, AuctionStyleEnum};
+    use crate::dice::DiceRoll;
+    use crate::location::LocationEnum;
+    use crate::test_support::get_new_board;
+
+    /// Bids the minimum needed until each player's limit is reached.
+    struct LimitBidder(HashMap<usize, i32>);
+    impl AuctionBidder for LimitBidder {
+        fn bid(&mut self, _: &BoardState, player_number: usize, _: LocationEnum, highest_bid: i32) -> Option<i32> {
+            let limit = *self.0.get(&player_number)?;
+            let bid = if highest_bid == 0 { 10 } else { highest_bid + 10 };
+            if bid <= limit { Some(bid) } else { None }
+        }
+    }
+
+    /// Bids the whole limit straight away.
+    struct SealedBidder(HashMap<usize, i32>);
+    impl AuctionBidder for SealedBidder {
+        fn bid(&mut self, _: &BoardState, player_number: usize, _: LocationEnum, _: i32) -> Option<i32> {
+            self.0.get(&player_number).copied()
+        }
+    }
+
+    #[test]
+    fn open_auction_goes_to_the_highest_bidder() {
+        let mut board = get_new_board(DiceRoll(1, 2), 3);
+        let mut bidder = LimitBidder(HashMap::from([(1, 100), (2, 150), (3, 125)]));
+        let result = board.auction_property(LocationEnum::Boardwalk, AuctionStyleEnum::OpenAscending, &mut bidder);
+        assert_eq!(result, Some(AuctionResult { location: LocationEnum::Boardwalk, winner: 2, price: 130 }));
+        assert_eq!(board.properties.get_property(LocationEnum::Boardwalk).owned_by_player_number, Some(2));
+        assert_eq!(board.players[&2].cash, 1370);
+    }
+
+    #[test]
+    fn sealed_auction_ignores_bids_over_cash_and_breaks_ties_in_turn_order() {
+        let mut board = get_new_board(DiceRoll(1, 2), 3);
+        board.get_current_player_by_number(1).cash = 100;
+        let mut bidder = SealedBidder(HashMap::from([(1, 300), (2, 200), (3, 200)]));
+        let result = board.auction_property(LocationEnum::Boardwalk, AuctionStyleEnum::Sealed, &mut bidder);
+        assert_eq!(result, Some(AuctionResult { location: LocationEnum::Boardwalk, winner: 2, price: 200 }));
+    }
+
+    #[test]
+    fn bids_below_the_minimum_increment_are_rejected() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        let mut bidder = SealedBidder(HashMap::from([(1, 5), (2, 9)]));
+        let result = board.auction_property(LocationEnum::Boardwalk, AuctionStyleEnum::Sealed, &mut bidder);
+        assert_eq!(result, None);
+        assert_eq!(board.properties.get_property(LocationEnum::Boardwalk).owned_by_player_number, None);
+    }
+
+    #[test]
+    fn declined_property_is_auctioned() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.get_current_player_by_number(1).cash = 50;
+        board.offer_purchase(1, LocationEnum::ParkPlace);
+        assert_eq!(board.properties.get_property(LocationEnum::ParkPlace).owned_by_player_number, Some(2));
+        assert_eq!(board.players[&2].cash, 1440);
+    }
+
+    #[test]
+    fn bank_auctions_a_bankrupt_players_property() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::MediterraneanAvenue).unwrap();
+        board.get_current_player_by_number(1).cash = 0;
+        board.charge(1, None, 1000);
+        assert_eq!(board.properties.get_property(LocationEnum::MediterraneanAvenue).owned_by_player_number, Some(2));
+        assert_eq!(board.players[&2].cash, 1490);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::board_state::BoardState;
+use crate::events::GameEvent;
+use crate::location::{LocationEnum, LocationTypeEnum};
+
+impl BoardState {
+    /// Collects `amount` from `debtor` for `creditor`, or for the bank when there is no creditor.
+    /// A debtor short of cash sells buildings and mortgages deeds first, and goes bankrupt if that
+    /// still does not cover the debt.
+    pub fn charge(&mut self, debtor: usize, creditor: Option<usize>, amount: i32) {
+        if amount <= 0 || self.players[&debtor].is_bankrupt {
+            return;
+        }
+
+        self.raise_funds(debtor, amount);
+        if self.players[&debtor].cash < amount {
+            self.declare_bankruptcy(debtor, creditor);
+            return;
+        }
+
+        self.get_current_player_by_number(debtor).cash -= amount;
+        if let Some(creditor) = creditor {
+            self.get_current_player_by_number(creditor).cash += amount;
+        }
+    }
+
+    /// Treats a negative balance left by a square or jail fine as a debt to the bank.
+    pub(crate) fn settle_bank_debt(&mut self, player_number: usize) {
+        let player = self.get_current_player_by_number(player_number);
+        if player.cash >= 0 {
+            return;
+        }
+
+        let owed = -player.cash;
+        player.cash = 0;
+        self.charge(player_number, None, owed);
+    }
+
+    /// Sells buildings, then mortgages deeds, until `player_number` holds at least `amount`.
+    pub fn raise_funds(&mut self, player_number: usize, amount: i32) {
+        while self.players[&player_number].cash < amount {
+            if !self.sell_one_building(player_number) && !self.mortgage_one_deed(player_number) {
+                return;
+            }
+        }
+    }
+
+    pub fn declare_bankruptcy(&mut self, debtor: usize, creditor: Option<usize>) {
+        while self.sell_one_building(debtor) {}
+
+        let player = self.get_current_player_by_number(debtor);
+        let cash = player.cash.max(0);
+        let cards: Vec<_> = player.get_out_of_jail_free_cards.drain(..).collect();
+        player.cash = 0;
+        player.is_bankrupt = true;
+        player.is_in_jail = false;
+        self.emit(GameEvent::Bankrupt { player_number: debtor, creditor });
+
+        let deeds = self.get_owned_deeds(debtor);
+        match creditor {
+            Some(creditor) => {
+                let creditor_player = self.get_current_player_by_number(creditor);
+                creditor_player.cash += cash;
+                creditor_player.get_out_of_jail_free_cards.extend(cards);
+                for location in deeds {
+                    let property = self.properties.get_property(location);
+                    property.owned_by_player_number = Some(creditor);
+                    // The creditor owes the bank interest on every mortgaged deed they inherit.
+                    if property.is_mortgaged {
+                        let interest = property.mortgage_interest();
+                        self.charge(creditor, None, interest);
+                    }
+                }
+            },
+            None => {
+                for card in cards {
+                    self.return_get_out_of_jail_free_card(card);
+                }
+                // The bank puts everything it takes back straight up for auction.
+                for location in deeds {
+                    let property = self.properties.get_property(location);
+                    property.owned_by_player_number = None;
+                    property.is_mortgaged = false;
+                    if self.rules.auctions {
+                        self.auction_to_controllers(location);
+                    }
+                }
+            },
+        }
+    }
+
+    /// The last solvent player, once everyone else has gone bankrupt.
+    pub fn get_winner(&self) -> Option<usize> {
+        let solvent: Vec<usize> = self.players.values()
+            .filter(|player| !player.is_bankrupt)
+            .map(|player| player.player_number)
+            .collect();
+
+        match solvent.as_slice() {
+            [winner] if self.players.len() > 1 => Some(*winner),
+            _ => None,
+        }
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.get_winner().is_some()
+    }
+
+    pub(crate) fn get_owned_deeds(&self, player_number: usize) -> Vec<LocationEnum> {
+        let mut deeds: Vec<LocationEnum> = self.properties.list.values()
+            .filter(|property| property.owned_by_player_number == Some(player_number))
+            .map(|property| property.location)
+            .collect();
+        deeds.sort();
+        deeds
+    }
+
+    // Sells from the most developed street so the even-build rule always holds.
+    fn sell_one_building(&mut self, player_number: usize) -> bool {
+        let street = self.get_owned_deeds(player_number).into_iter()
+            .filter(|location| location.get_location_type() == LocationTypeEnum::Property)
+            .max_by_key(|location| self.properties.get_property_ref(*location).building_level());
+        let location = match street {
+            Some(location) if self.properties.get_property_ref(location).building_level() > 0 => location,
+            _ => return false,
+        };
+
+        let property = self.properties.get_property_ref(location);
+        let (hotels, house_cost) = (property.hotels, property.house_cost);
+        if hotels == 0 {
+            return self.sell_house(player_number, location).is_ok();
+        }
+        if self.sell_hotel(player_number, location).is_ok() {
+            return true;
+        }
+
+        // With no houses to break the hotel down into it goes back to the bank whole,
+        // refunding half of the hotel and of the four houses it replaced.
+        let refund = house_cost / 2 * 5;
+        self.properties.get_property(location).hotels = 0;
+        self.hotels_available += 1;
+        self.get_current_player_by_number(player_number).cash += refund;
+        true
+    }
+
+    fn mortgage_one_deed(&mut self, player_number: usize) -> bool {
+        self.get_owned_deeds(player_number).into_iter()
+            .any(|location| self.mortgage_property(player_number, location).is_ok())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::DeckEnum;
+    use crate::dice::DiceRoll;
+    use crate::location::{ColorGroupEnum, LocationEnum};
+    use crate::test_support::get_new_board;
+
+    #[test]
+    fn charge_moves_cash_between_players() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.charge(1, Some(2), 300);
+        assert_eq!(board.players[&1].cash, 1200);
+        assert_eq!(board.players[&2].cash, 1800);
+    }
+
+    #[test]
+    fn short_player_sells_buildings_before_mortgaging() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        for location in ColorGroupEnum::Brown.get_locations() {
+            board.buy_property(1, location).unwrap();
+        }
+        for location in ColorGroupEnum::Brown.get_locations() {
+            board.buy_house(1, location).unwrap();
+        }
+        board.buy_property(1, LocationEnum::ReadingRailroad).unwrap();
+        board.get_current_player_by_number(1).cash = 0;
+
+        board.charge(1, None, 40);
+        assert_eq!(board.properties.count_buildings(1), (0, 0));
+        assert_eq!(board.players[&1].cash, 10);
+        assert!(!board.properties.get_property(LocationEnum::ReadingRailroad).is_mortgaged);
+
+        board.charge(1, None, 50);
+        assert!(board.properties.get_property(LocationEnum::MediterraneanAvenue).is_mortgaged);
+        assert_eq!(board.players[&1].cash, 10 + 30 + 30 - 50);
+        assert!(!board.players[&1].is_bankrupt);
+    }
+
+    #[test]
+    fn bankrupt_player_assets_go_to_the_creditor() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::Boardwalk).unwrap();
+        board.mortgage_property(1, LocationEnum::Boardwalk).unwrap();
+        let player = board.get_current_player_by_number(1);
+        player.cash = 100;
+        player.get_out_of_jail_free_cards.push(DeckEnum::Chance);
+
+        board.charge(1, Some(2), 500);
+        assert!(board.players[&1].is_bankrupt);
+        assert_eq!(board.players[&1].cash, 0);
+        let property = board.properties.get_property(LocationEnum::Boardwalk);
+        assert_eq!(property.owned_by_player_number, Some(2));
+        assert!(property.is_mortgaged);
+        assert_eq!(board.players[&2].cash, 1500 + 100 - 20);
+        assert_eq!(board.players[&2].get_out_of_jail_free_cards, vec![DeckEnum::Chance]);
+    }
+
+    #[test]
+    fn bankruptcy_to_the_bank_returns_property_unmortgaged() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::Boardwalk).unwrap();
+        board.mortgage_property(1, LocationEnum::Boardwalk).unwrap();
+        board.get_current_player_by_number(1).cash = 0;
+
+        board.get_current_player_by_number(2).cash = 0;
+        board.charge(1, None, 500);
+        assert!(board.players[&1].is_bankrupt);
+        let property = board.properties.get_property(LocationEnum::Boardwalk);
+        assert_eq!(property.owned_by_player_number, None);
+        assert!(!property.is_mortgaged);
+    }
+
+    #[test]
+    fn bankrupt_players_leave_the_turn_rotation() {
+        let mut board = get_new_board(DiceRoll(1, 2), 3);
+        board.get_current_player_by_number(2).is_bankrupt = true;
+        board.get_current_player_by_number(1).current_location = LocationEnum::Jail;
+        board.get_current_player_by_number(1).is_in_jail = true;
+        board.take_turn();
+        assert_eq!(board.player_turn, 3);
+    }
+
+    #[test]
+    fn last_solvent_player_wins() {
+        let mut board = get_new_board(DiceRoll(1, 2), 3);
+        assert_eq!(board.get_winner(), None);
+        board.get_current_player_by_number(1).cash = 0;
+        board.charge(1, Some(3), 100);
+        assert_eq!(board.get_winner(), None);
+        board.get_current_player_by_number(2).cash = 0;
+        board.charge(2, None, 100);
+        assert_eq!(board.get_winner(), Some(3));
+        assert!(board.is_game_over());
+    }
+}
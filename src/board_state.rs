@@ -2,27 +2,71 @@ use std::cell::RefCell;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::rc::Rc;
-use crate::dice::{RollEngine, DiceRoll};
-use crate::location::Properties;
+use crate::cards::{ChanceCardEnum, CommunityChestCardEnum, Deck};
+use crate::controller::PlayerController;
+use rand_pcg::Pcg32;
+use crate::dice::{RollEngine, DiceRoll, Roll, SeededDice, SpeedDieFace};
+use crate::error::GameError;
+use crate::events::{EventSubscriber, GameEvent};
+use crate::jail::JailOptionEnum;
+use crate::location::{LocationEnum, Properties};
+use crate::rules::GameRules;
+use crate::square_action::Landing;
 use crate::Player;
 
+pub const BANK_HOUSES: i32 = 32;
+pub const BANK_HOTELS: i32 = 12;
+/// PCG stream the decks are shuffled from in a seeded game.
+pub const DECK_STREAM: u64 = 0x5851f42d4c957f2d;
+
 pub struct BoardState {
     pub player_turn: usize,
     pub players: HashMap<usize, Player>,
     pub roll_engine: Rc<RefCell<dyn RollEngine>>,
     pub properties: Properties,
+    pub houses_available: i32,
+    pub hotels_available: i32,
+    pub chance_deck: Deck<ChanceCardEnum>,
+    pub community_chest_deck: Deck<CommunityChestCardEnum>,
+    pub controllers: HashMap<usize, Rc<RefCell<dyn PlayerController>>>,
+    /// The seed dice and decks were drawn from, when the game is reproducible.
+    pub seed: Option<u64>,
+    pub events: Vec<GameEvent>,
+    pub subscribers: Vec<Rc<RefCell<dyn EventSubscriber>>>,
+    pub rules: GameRules,
+    pub free_parking_pot: i32,
 }
 
 impl BoardState {
-    pub fn new(roll_engine: Rc<RefCell<dyn RollEngine>>) -> Self {
+    pub fn new(roll_engine: Rc<RefCell<dyn RollEngine>>, rules: GameRules) -> Self {
         BoardState {
             player_turn: 1,
             players: HashMap::new(),
             roll_engine,
-            properties: Properties::get_new_list()
+            properties: Properties::get_new_list(),
+            houses_available: rules.bank_houses,
+            hotels_available: rules.bank_hotels,
+            chance_deck: Deck::new_shuffled(ChanceCardEnum::get_deck()),
+            community_chest_deck: Deck::new_shuffled(CommunityChestCardEnum::get_deck()),
+            controllers: HashMap::new(),
+            seed: None,
+            events: Vec::new(),
+            subscribers: Vec::new(),
+            free_parking_pot: if rules.free_parking_jackpot { rules.free_parking_seed } else { 0 },
+            rules,
         }
     }
 
+    /// A game whose rolls and deck order all follow from `seed`, so it can be replayed exactly.
+    pub fn new_seeded(seed: u64, rules: GameRules) -> Self {
+        let mut board = BoardState::new(Rc::new(RefCell::new(SeededDice::new(seed))), rules);
+        let mut rng = Pcg32::new(seed, DECK_STREAM);
+        board.chance_deck = Deck::new_seeded(ChanceCardEnum::get_deck(), &mut rng);
+        board.community_chest_deck = Deck::new_seeded(CommunityChestCardEnum::get_deck(), &mut rng);
+        board.seed = Some(seed);
+        board
+    }
+
     pub fn add_players(&mut self, players: Vec<Player>) {
         for player in players.into_iter() {
             self.add_player(player);
@@ -33,20 +77,125 @@ impl BoardState {
         self.players.insert(self.players.len() + 1, player);
     }
 
+    /// Adds `player_count` new players, each with the starting cash these rules give.
+    pub fn seat_new_players(&mut self, player_count: i32) {
+        for _ in 0..player_count {
+            let player = Player::new(self.players.len() + 1, &self.rules);
+            self.add_player(player);
+        }
+    }
+
     pub fn take_turn(&mut self) {
-        let roll = self.roll_engine.borrow().roll();
-        let current_player = self.get_current_player();
+        let player_number = self.player_turn;
+        let rules = self.rules.clone();
+        self.emit(GameEvent::TurnStarted { player_number });
+        self.offer_jail_options(player_number);
+        let dice = self.roll_engine.borrow_mut().roll();
+        self.emit(GameEvent::Rolled { player_number, roll: dice.clone() });
+        let was_in_jail = self.players[&player_number].is_in_jail;
+        // The speed die is left out of rolls to get out of jail.
+        let speed_die = if rules.speed_die && !was_in_jail { self.roll_speed_die(player_number) } else { None };
+        let roll = Roll { dice, speed_die };
+        // Doubles rolled to get out of jail do not earn another roll, and triples end the turn.
+        let rolls_again = roll.dice.is_doubles() && !was_in_jail && !roll.is_triples();
+
+        if roll.is_triples() {
+            self.get_current_player().doubles_roll_count = 0;
+            self.move_anywhere(player_number, &roll.dice);
+        } else if self.get_current_player().record_doubles(&roll.dice) {
+            self.get_current_player().send_to_jail();
+            self.emit(GameEvent::SentToJail { player_number });
+        } else {
+            let last_location = self.players[&player_number].current_location;
+            if roll.speed_die.is_some() {
+                let spaces = self.get_speed_die_move(player_number, &roll);
+                self.get_current_player().move_forward(spaces, &rules);
+            } else {
+                self.get_current_player().update_location(&roll.dice, &rules);
+            }
+            let current_player = &self.players[&player_number];
+            let player_location = current_player.current_location;
+            if was_in_jail && !current_player.is_in_jail {
+                let how = if roll.dice.is_doubles() { JailOptionEnum::RollForDoubles } else { JailOptionEnum::PayFine };
+                self.emit(GameEvent::LeftJail { player_number, how });
+            }
+            self.record_move(player_number, last_location);
+            self.settle_bank_debt(player_number);
+            self.resolve_landing(player_location, &roll.dice);
+            if roll.speed_die == Some(SpeedDieFace::MrMonopoly) {
+                self.follow_mr_monopoly(player_number, &roll.dice);
+            }
+        }
+
+        if !self.players[&self.player_turn].is_bankrupt {
+            self.run_turn_actions(self.player_turn);
+        }
+        self.end_turn(rolls_again);
+    }
+
+    fn end_turn(&mut self, rolls_again: bool) {
+        let next_player_turn = self.get_next_player_turn(rolls_again);
+        if next_player_turn != self.player_turn {
+            self.get_current_player().doubles_roll_count = 0;
+        }
+        self.player_turn = next_player_turn;
+    }
+
+    pub fn buy_property(&mut self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        let property = self.properties.get_property_ref(location);
+        if !property.is_purchasable() {
+            return Err(GameError::PropertyNotPurchasable(location));
+        }
+        if property.owned_by_player_number.is_some() {
+            return Err(GameError::PropertyAlreadyOwned(location));
+        }
+
+        let cost = property.cost;
+        let player = self.get_current_player_by_number(player_number);
+        if player.cash < cost {
+            return Err(GameError::InsufficientFunds { needed: cost, available: player.cash });
+        }
+        player.cash -= cost;
+        self.properties.get_property(location).owned_by_player_number = Some(player_number);
+        self.emit(GameEvent::PropertyPurchased { player_number, location, price: cost });
+        Ok(())
+    }
 
-        current_player.update_location(&roll);
-        let player_location = current_player.current_location.clone();
-        // get the next property.
-        let property = self.properties.get_property(player_location);
-        property.run_fn(current_player);
+    pub fn transfer_cash(&mut self, from_player_number: usize, to_player_number: usize, amount: i32) {
+        self.charge(from_player_number, Some(to_player_number), amount);
+    }
+
+    /// Charges rent for `location`, logging it once the rent has been paid in full.
+    pub(crate) fn pay_rent(&mut self, player_number: usize, owner: usize, location: LocationEnum, amount: i32) {
+        if amount <= 0 || (self.rules.no_rent_in_jail && self.players[&owner].is_in_jail) {
+            return;
+        }
+
+        self.transfer_cash(player_number, owner, amount);
+        if !self.players[&player_number].is_bankrupt {
+            self.emit(GameEvent::RentPaid { player_number, owner, location, amount });
+        }
+    }
 
-        self.player_turn = self.get_next_player_turn(&roll);
+    pub(crate) fn resolve_landing(&mut self, location: LocationEnum, roll: &DiceRoll) {
+        if self.players[&self.player_turn].is_bankrupt {
+            return;
+        }
+
+        let landing = Landing { player_number: self.player_turn, location, roll: roll.clone() };
+        let action = self.properties.get_property_ref(location).get_action();
+        action.on_land(self, &landing);
+    }
+
+    pub(crate) fn offer_purchase(&mut self, player_number: usize, location: LocationEnum) {
+        let controller = self.get_player_controller(player_number);
+        let wants_to_buy = controller.borrow_mut().should_buy(self, player_number, location);
+        if (!wants_to_buy || self.buy_property(player_number, location).is_err()) && self.rules.auctions {
+            self.auction_to_controllers(location);
+        }
     }
 
-    fn get_current_player_by_number(&mut self, player_number: usize) -> &mut Player {
+    pub(crate) fn get_current_player_by_number(&mut self, player_number: usize) -> &mut Player {
         match self.players.entry(player_number) {
             Entry::Occupied(o) => o.into_mut(),
             Entry::Vacant(_) => panic!("Player not found!"),
@@ -57,12 +206,21 @@ impl BoardState {
         self.get_current_player_by_number(self.player_turn)
     }
 
-    fn get_next_player_turn(&self, roll: &DiceRoll) -> usize {
-        if roll.is_doubles() {
+    fn get_next_player_turn(&self, rolls_again: bool) -> usize {
+        // Doubles earn another roll unless they ended with a trip to jail.
+        let current_player = &self.players[&self.player_turn];
+        if rolls_again && !current_player.is_in_jail && !current_player.is_bankrupt {
             return self.player_turn;
         }
 
-        self.player_turn % self.players.len() + 1
+        let mut next_player_turn = self.player_turn;
+        for _ in 0..self.players.len() {
+            next_player_turn = next_player_turn % self.players.len() + 1;
+            if !self.players[&next_player_turn].is_bankrupt {
+                return next_player_turn;
+            }
+        }
+        self.player_turn
     }
 }
 
@@ -70,9 +228,12 @@ impl BoardState {
 mod test {
     use std::cell::RefCell;
     use std::rc::Rc;
-    use crate::{BoardState, Player};
+    use crate::BoardState;
+    use crate::cards::{CommunityChestCardEnum, Deck};
     use crate::dice::{DiceRoll, RollEngine};
+    use crate::error::GameError;
     use crate::location::LocationEnum;
+    use crate::rules::GameRules;
 
     struct MockContext {
         roll_engine: Rc<RefCell<MockedRollEngine>>,
@@ -81,17 +242,15 @@ mod test {
     struct MockedRollEngine(DiceRoll);
     impl MockedRollEngine {
         pub fn new() -> Self {
-            Self {
-                0: DiceRoll(1, 1),
-            }
+            Self(DiceRoll(1, 1))
         }
         pub fn set_roll(&mut self, first: i32, second: i32) {
             self.0 = DiceRoll(first, second);
         }
     }
     impl RollEngine for MockedRollEngine {
-        fn roll(&self) -> DiceRoll {
-            return self.0.clone();
+        fn roll(&mut self) -> DiceRoll {
+            self.0.clone()
         }
     }
 
@@ -102,8 +261,8 @@ mod test {
             roll_engine: mocked_roll_engine.clone()
         };
 
-        let mut board = BoardState::new(mocked_roll_engine.clone());
-        board.add_players(Player::create_players(player_count));
+        let mut board = BoardState::new(mocked_roll_engine.clone(), GameRules::default());
+        board.seat_new_players(player_count);
 
         (mock_context, board)
     }
@@ -129,6 +288,7 @@ mod test {
     fn next_player_turn_is_same_if_rolled_doubles() {
         let (context, mut board) = get_new_board(5);
         (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 1);
+        board.community_chest_deck = Deck::new(vec![CommunityChestCardEnum::GetOutOfJailFree]);
         board.take_turn();
         assert_eq!(1, board.player_turn);
     }
@@ -150,7 +310,7 @@ mod test {
         (context.roll_engine.as_ref()).borrow_mut().set_roll(2, 3);
         let player = board.get_current_player();
         player.send_to_jail();
-        for _ in 0..3 {
+        for _ in 0..2 {
             board.take_turn();
             board.player_turn = 1;
             let player = board.get_current_player();
@@ -159,8 +319,9 @@ mod test {
         board.take_turn();
         let player = board.get_current_player_by_number(1);
         assert_eq!(player.current_location, LocationEnum::PennsylvaniaRailroad);
-        assert_eq!(player.is_in_jail, false);
+        assert!(!player.is_in_jail);
         assert_eq!(player.doubles_roll_jail_count, 0);
+        assert_eq!(player.cash, 1500 - 50 - 200);
     }
 
     #[test]
@@ -172,14 +333,16 @@ mod test {
         board.take_turn();
         let player = board.get_current_player_by_number(1);
         assert_eq!(player.current_location, LocationEnum::VirginiaAvenue);
-        assert_eq!(player.is_in_jail, false);
+        assert!(!player.is_in_jail);
         assert_eq!(player.doubles_roll_jail_count, 0);
+        assert_eq!(board.player_turn, 2);
     }
 
     #[test]
     fn if_player_rolls_past_go_they_gain_200_cash() {
         let (context, mut board) = get_new_board(5);
         (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 2);
+        board.community_chest_deck = Deck::new(vec![CommunityChestCardEnum::GetOutOfJailFree]);
         let player = board.get_current_player();
         player.current_location = LocationEnum::Boardwalk;
         board.take_turn();
@@ -190,5 +353,137 @@ mod test {
 
     #[test]
     fn if_player_goes_to_jail_they_do_not_gain_200_cash() {
+        let (context, mut board) = get_new_board(5);
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 2);
+        let player = board.get_current_player();
+        player.current_location = LocationEnum::VentnorAvenue;
+        board.take_turn();
+        let player = board.get_current_player_by_number(1);
+        assert_eq!(player.current_location, LocationEnum::Jail);
+        assert!(player.is_in_jail);
+        assert_eq!(player.cash, 1500);
+        assert_eq!(board.player_turn, 2);
+    }
+
+    #[test]
+    fn tax_squares_charge_the_player() {
+        let (context, mut board) = get_new_board(2);
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 3);
+        board.take_turn();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1300);
+        board.get_current_player_by_number(2).current_location = LocationEnum::PennsylvaniaAvenue;
+        board.take_turn();
+        assert_eq!(board.get_current_player_by_number(2).cash, 1400);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn rolling_doubles_three_times_sends_player_to_jail() {
+        let (context, mut board) = get_new_board(2);
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(2, 2);
+        board.take_turn();
+        board.take_turn();
+        assert_eq!(board.player_turn, 1);
+        assert_eq!(board.get_current_player().doubles_roll_count, 2);
+        board.take_turn();
+        let player = board.get_current_player_by_number(1);
+        assert_eq!(player.current_location, LocationEnum::Jail);
+        assert!(player.is_in_jail);
+        assert_eq!(player.doubles_roll_count, 0);
+        assert_eq!(player.cash, 1200);
+        assert_eq!(board.player_turn, 2);
+    }
+
+    #[test]
+    fn landing_on_unowned_property_buys_it() {
+        let (context, mut board) = get_new_board(2);
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 2);
+        board.take_turn();
+        let property = board.properties.get_property(LocationEnum::BalticAvenue);
+        assert_eq!(property.owned_by_player_number, Some(1));
+        assert_eq!(board.get_current_player_by_number(1).cash, 1440);
+    }
+
+    #[test]
+    fn cannot_buy_property_without_enough_cash() {
+        let (_, mut board) = get_new_board(2);
+        board.get_current_player_by_number(1).cash = 100;
+        let result = board.buy_property(1, LocationEnum::Boardwalk);
+        assert_eq!(result, Err(GameError::InsufficientFunds { needed: 400, available: 100 }));
+        assert_eq!(board.properties.get_property(LocationEnum::Boardwalk).owned_by_player_number, None);
+    }
+
+    #[test]
+    fn cannot_buy_property_that_is_already_owned() {
+        let (_, mut board) = get_new_board(2);
+        board.buy_property(2, LocationEnum::Boardwalk).unwrap();
+        let result = board.buy_property(1, LocationEnum::Boardwalk);
+        assert_eq!(result, Err(GameError::PropertyAlreadyOwned(LocationEnum::Boardwalk)));
+    }
+
+    #[test]
+    fn landing_on_owned_property_pays_rent_to_owner() {
+        let (context, mut board) = get_new_board(2);
+        board.buy_property(2, LocationEnum::BalticAvenue).unwrap();
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 2);
+        board.take_turn();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1496);
+        assert_eq!(board.get_current_player_by_number(2).cash, 1444);
+    }
+
+    #[test]
+    fn railroad_rent_scales_with_railroads_owned() {
+        let (context, mut board) = get_new_board(2);
+        board.buy_property(2, LocationEnum::ReadingRailroad).unwrap();
+        board.buy_property(2, LocationEnum::ShortLine).unwrap();
+        board.buy_property(2, LocationEnum::BnORailroad).unwrap();
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(2, 3);
+        board.take_turn();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1400);
+    }
+
+    #[test]
+    fn utility_rent_is_a_multiple_of_the_roll() {
+        let (context, mut board) = get_new_board(2);
+        board.buy_property(2, LocationEnum::ElectricCompany).unwrap();
+        board.get_current_player_by_number(1).current_location = LocationEnum::Jail;
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 1);
+        board.take_turn();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1492);
+        board.buy_property(2, LocationEnum::WaterWorks).unwrap();
+        board.get_current_player_by_number(1).current_location = LocationEnum::Jail;
+        board.take_turn();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1472);
+    }
+
+    #[test]
+    fn landing_on_boardwalk_does_not_wrap_to_go() {
+        let (context, mut board) = get_new_board(2);
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 2);
+        board.get_current_player().current_location = LocationEnum::PennsylvaniaAvenue;
+        board.get_current_player().cash = 300;
+        board.take_turn();
+        let player = &board.players[&1];
+        assert_eq!(player.current_location, LocationEnum::ParkPlace);
+        assert_eq!(player.cash, 300);
+        board.player_turn = 1;
+        (context.roll_engine.as_ref()).borrow_mut().set_roll(1, 1);
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::Boardwalk);
+        assert_eq!(board.players[&1].cash, 300);
+    }
+
+    #[test]
+    fn seeded_games_deal_and_roll_identically() {
+        let deal = || {
+            let board = BoardState::new_seeded(7, GameRules::default());
+            let rolls: Vec<(i32, i32)> = (0..20)
+                .map(|_| { let roll = board.roll_engine.borrow_mut().roll(); (roll.0, roll.1) })
+                .collect();
+            (board.seed, rolls, board.chance_deck.cards.clone(), board.community_chest_deck.cards.clone())
+        };
+        let first = deal();
+        assert_eq!(first.0, Some(7));
+        assert_eq!(first, deal());
+        assert_ne!(first.2, BoardState::new_seeded(8, GameRules::default()).chance_deck.cards);
+    }
+}
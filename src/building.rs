@@ -0,0 +1,255 @@
+use crate::board_state::BoardState;
+use crate::error::GameError;
+use crate::events::GameEvent;
+use crate::location::{ColorGroupEnum, LocationEnum, LocationTypeEnum};
+
+impl BoardState {
+    pub fn buy_house(&mut self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        let group = self.check_can_build_on(player_number, location)?;
+        let property = self.properties.get_property_ref(location);
+        if property.hotels > 0 || property.houses >= 4 {
+            return Err(GameError::BuildingLimitReached(location));
+        }
+        if property.building_level() > self.lowest_building_level(group) {
+            return Err(GameError::UnevenBuilding(location));
+        }
+        if self.houses_available == 0 {
+            return Err(GameError::HouseShortage);
+        }
+
+        let house_cost = property.house_cost;
+        self.pay_for_building(player_number, house_cost)?;
+        self.properties.get_property(location).houses += 1;
+        self.houses_available -= 1;
+        self.emit(GameEvent::HouseBuilt { player_number, location });
+        Ok(())
+    }
+
+    /// Swaps the four houses on `location` for a hotel, returning the houses to the bank.
+    pub fn buy_hotel(&mut self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        let group = self.check_can_build_on(player_number, location)?;
+        let property = self.properties.get_property_ref(location);
+        if property.hotels > 0 {
+            return Err(GameError::BuildingLimitReached(location));
+        }
+        if property.houses < 4 || self.lowest_building_level(group) < 4 {
+            return Err(GameError::UnevenBuilding(location));
+        }
+        if self.hotels_available == 0 {
+            return Err(GameError::HotelShortage);
+        }
+
+        let house_cost = property.house_cost;
+        self.pay_for_building(player_number, house_cost)?;
+        let property = self.properties.get_property(location);
+        property.houses = 0;
+        property.hotels = 1;
+        self.houses_available += 4;
+        self.hotels_available -= 1;
+        self.emit(GameEvent::HotelBuilt { player_number, location });
+        Ok(())
+    }
+
+    /// Sells a house back to the bank for half its cost.
+    pub fn sell_house(&mut self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        let group = self.check_owns_street(player_number, location)?;
+        let property = self.properties.get_property_ref(location);
+        if property.houses == 0 {
+            return Err(GameError::NoBuildings(location));
+        }
+        if property.building_level() < self.highest_building_level(group) {
+            return Err(GameError::UnevenBuilding(location));
+        }
+
+        let refund = property.house_cost / 2;
+        self.properties.get_property(location).houses -= 1;
+        self.houses_available += 1;
+        self.get_current_player_by_number(player_number).cash += refund;
+        self.emit(GameEvent::HouseSold { player_number, location });
+        Ok(())
+    }
+
+    /// Sells a hotel back to the bank for half its cost, leaving four houses in its place.
+    pub fn sell_hotel(&mut self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        self.check_owns_street(player_number, location)?;
+        let property = self.properties.get_property_ref(location);
+        if property.hotels == 0 {
+            return Err(GameError::NoBuildings(location));
+        }
+        if self.houses_available < 4 {
+            return Err(GameError::HouseShortage);
+        }
+
+        let refund = property.house_cost / 2;
+        let property = self.properties.get_property(location);
+        property.hotels = 0;
+        property.houses = 4;
+        self.hotels_available += 1;
+        self.houses_available -= 4;
+        self.get_current_player_by_number(player_number).cash += refund;
+        self.emit(GameEvent::HotelSold { player_number, location });
+        Ok(())
+    }
+
+    fn check_owns_street(&self, player_number: usize, location: LocationEnum) -> Result<ColorGroupEnum, GameError> {
+        let property = self.properties.get_property_ref(location);
+        if property.location_type != LocationTypeEnum::Property {
+            return Err(GameError::NotBuildable(location));
+        }
+        if property.owned_by_player_number != Some(player_number) {
+            return Err(GameError::NotOwner(location));
+        }
+
+        property.get_color_group().ok_or(GameError::NotBuildable(location))
+    }
+
+    fn check_can_build_on(&self, player_number: usize, location: LocationEnum) -> Result<ColorGroupEnum, GameError> {
+        let group = self.check_owns_street(player_number, location)?;
+        if !self.properties.has_monopoly(player_number, group) {
+            return Err(GameError::NoMonopoly(group));
+        }
+        if let Some(mortgaged) = group.get_locations().into_iter()
+            .find(|location| self.properties.get_property_ref(*location).is_mortgaged) {
+            return Err(GameError::Mortgaged(mortgaged));
+        }
+
+        Ok(group)
+    }
+
+    fn pay_for_building(&mut self, player_number: usize, cost: i32) -> Result<(), GameError> {
+        let player = self.get_current_player_by_number(player_number);
+        if player.cash < cost {
+            return Err(GameError::InsufficientFunds { needed: cost, available: player.cash });
+        }
+        player.cash -= cost;
+        Ok(())
+    }
+
+    fn lowest_building_level(&self, group: ColorGroupEnum) -> i32 {
+        group.get_locations().into_iter()
+            .map(|location| self.properties.get_property_ref(location).building_level())
+            .min()
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn highest_building_level(&self, group: ColorGroupEnum) -> i32 {
+        group.get_locations().into_iter()
+            .map(|location| self.properties.get_property_ref(location).building_level())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::BoardState;
+    use crate::dice::DiceRoll;
+    use crate::error::GameError;
+    use crate::location::{ColorGroupEnum, LocationEnum};
+    use crate::test_support::get_new_board;
+
+    fn get_board_with_monopoly(group: ColorGroupEnum) -> BoardState {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        for location in group.get_locations() {
+            board.properties.get_property(location).owned_by_player_number = Some(1);
+        }
+        board
+    }
+
+    #[test]
+    fn can_build_houses_evenly_on_a_monopoly() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::DarkBlue);
+        board.buy_house(1, LocationEnum::ParkPlace).unwrap();
+        board.buy_house(1, LocationEnum::Boardwalk).unwrap();
+        board.buy_house(1, LocationEnum::Boardwalk).unwrap();
+        assert_eq!(board.get_current_player_by_number(1).cash, 900);
+        assert_eq!(board.houses_available, 29);
+        assert_eq!(board.properties.get_rent(LocationEnum::ParkPlace, 7), 175);
+        assert_eq!(board.properties.get_rent(LocationEnum::Boardwalk, 7), 600);
+    }
+
+    #[test]
+    fn cannot_build_unevenly() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::Brown);
+        board.buy_house(1, LocationEnum::BalticAvenue).unwrap();
+        let result = board.buy_house(1, LocationEnum::BalticAvenue);
+        assert_eq!(result, Err(GameError::UnevenBuilding(LocationEnum::BalticAvenue)));
+    }
+
+    #[test]
+    fn cannot_build_without_a_monopoly() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::Brown);
+        board.properties.get_property(LocationEnum::BalticAvenue).owned_by_player_number = Some(2);
+        let result = board.buy_house(1, LocationEnum::MediterraneanAvenue);
+        assert_eq!(result, Err(GameError::NoMonopoly(ColorGroupEnum::Brown)));
+    }
+
+    #[test]
+    fn hotel_requires_four_houses_on_every_street_in_the_group() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::Brown);
+        for _ in 0..4 {
+            board.buy_house(1, LocationEnum::MediterraneanAvenue).unwrap();
+            if board.properties.get_property(LocationEnum::BalticAvenue).houses < 3 {
+                board.buy_house(1, LocationEnum::BalticAvenue).unwrap();
+            }
+        }
+        let result = board.buy_hotel(1, LocationEnum::MediterraneanAvenue);
+        assert_eq!(result, Err(GameError::UnevenBuilding(LocationEnum::MediterraneanAvenue)));
+
+        board.buy_house(1, LocationEnum::BalticAvenue).unwrap();
+        board.buy_hotel(1, LocationEnum::MediterraneanAvenue).unwrap();
+        assert_eq!(board.houses_available, 28);
+        assert_eq!(board.hotels_available, 11);
+        assert_eq!(board.properties.get_rent(LocationEnum::MediterraneanAvenue, 7), 250);
+    }
+
+    #[test]
+    fn cannot_build_while_any_street_in_the_group_is_mortgaged() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::DarkBlue);
+        board.properties.get_property(LocationEnum::ParkPlace).is_mortgaged = true;
+        let result = board.buy_house(1, LocationEnum::Boardwalk);
+        assert_eq!(result, Err(GameError::Mortgaged(LocationEnum::ParkPlace)));
+    }
+
+    #[test]
+    fn cannot_build_when_the_bank_runs_out_of_houses() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::DarkBlue);
+        board.houses_available = 0;
+        let result = board.buy_house(1, LocationEnum::Boardwalk);
+        assert_eq!(result, Err(GameError::HouseShortage));
+    }
+
+    #[test]
+    fn selling_buildings_refunds_half_and_must_be_even() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::DarkBlue);
+        board.buy_house(1, LocationEnum::ParkPlace).unwrap();
+        board.buy_house(1, LocationEnum::Boardwalk).unwrap();
+        board.buy_house(1, LocationEnum::Boardwalk).unwrap();
+        let result = board.sell_house(1, LocationEnum::ParkPlace);
+        assert_eq!(result, Err(GameError::UnevenBuilding(LocationEnum::ParkPlace)));
+
+        board.sell_house(1, LocationEnum::Boardwalk).unwrap();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1500 - 600 + 100);
+        assert_eq!(board.houses_available, 30);
+    }
+
+    #[test]
+    fn selling_a_hotel_leaves_four_houses() {
+        let mut board = get_board_with_monopoly(ColorGroupEnum::DarkBlue);
+        board.get_current_player_by_number(1).cash = 5000;
+        for _ in 0..4 {
+            board.buy_house(1, LocationEnum::ParkPlace).unwrap();
+            board.buy_house(1, LocationEnum::Boardwalk).unwrap();
+        }
+        board.buy_hotel(1, LocationEnum::Boardwalk).unwrap();
+        board.houses_available = 3;
+        assert_eq!(board.sell_hotel(1, LocationEnum::Boardwalk), Err(GameError::HouseShortage));
+
+        board.houses_available = 4;
+        board.sell_hotel(1, LocationEnum::Boardwalk).unwrap();
+        let property = board.properties.get_property(LocationEnum::Boardwalk);
+        assert_eq!((property.houses, property.hotels), (4, 0));
+        assert_eq!(board.houses_available, 0);
+        assert_eq!(board.hotels_available, 12);
+    }
+}
@@ -1,3 +1,18 @@
+use std::collections::VecDeque;
+use rand::seq::SliceRandom;
+use rand_pcg::Pcg32;
+use serde::{Deserialize, Serialize};
+use crate::board_state::BoardState;
+use crate::dice::{uniform_below, DiceRoll};
+use crate::events::GameEvent;
+use crate::location::{LocationEnum, LocationTypeEnum, BOARD_SIZE};
+
+/// "Advance to nearest railroad" charges this many times the usual rent.
+pub const NEAREST_RAILROAD_MULTIPLIER: i32 = 2;
+/// "Advance to nearest utility" charges this many times a fresh roll, however many utilities are owned.
+pub const NEAREST_UTILITY_MULTIPLIER: i32 = 10;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ChanceCardEnum
 {
     AdvanceToGo,
@@ -6,14 +21,429 @@ pub enum ChanceCardEnum
     AdvanceToNearestUtility,
     AdvanceToNearestRailroad,
     AdvanceToIllinoisAvenue,
+    AdvanceToReadingRailroad,
     GoBackThreeSpaces,
     GoToJail,
-    GetOutOfJailFree
+    GetOutOfJailFree,
+    BankPaysDividend,
+    GeneralRepairs,
+    SpeedingFine,
+    ChairmanOfTheBoard,
+    BuildingLoanMatures,
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum CommunityChestCardEnum
 {
     AdvanceToGo,
     GoToJail,
-    GetOutOfJailFree
-}
\ No newline at end of file
+    GetOutOfJailFree,
+    BankError,
+    DoctorsFee,
+    SaleOfStock,
+    HolidayFundMatures,
+    IncomeTaxRefund,
+    Birthday,
+    LifeInsuranceMatures,
+    HospitalFees,
+    SchoolFees,
+    ConsultancyFee,
+    StreetRepairs,
+    BeautyContest,
+    Inheritance,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum DeckEnum {
+    Chance,
+    CommunityChest,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CardAction {
+    /// Move forward to the square, collecting Go salary on the way past.
+    AdvanceTo(LocationEnum),
+    /// Move forward to the next railroad or utility. An owned one charges the special card rent.
+    AdvanceToNearest(LocationTypeEnum),
+    GoBack(i32),
+    GoToJail,
+    GetOutOfJailFree,
+    Collect(i32),
+    Pay(i32),
+    Repairs { per_house: i32, per_hotel: i32 },
+    PayEachPlayer(i32),
+    CollectFromEachPlayer(i32),
+}
+
+impl ChanceCardEnum {
+    pub fn get_deck() -> Vec<ChanceCardEnum> {
+        vec![
+            ChanceCardEnum::AdvanceToBoardwalk,
+            ChanceCardEnum::AdvanceToGo,
+            ChanceCardEnum::AdvanceToIllinoisAvenue,
+            ChanceCardEnum::AdvanceToStCharlesPlace,
+            ChanceCardEnum::AdvanceToNearestRailroad,
+            ChanceCardEnum::AdvanceToNearestRailroad,
+            ChanceCardEnum::AdvanceToNearestUtility,
+            ChanceCardEnum::BankPaysDividend,
+            ChanceCardEnum::GetOutOfJailFree,
+            ChanceCardEnum::GoBackThreeSpaces,
+            ChanceCardEnum::GoToJail,
+            ChanceCardEnum::GeneralRepairs,
+            ChanceCardEnum::SpeedingFine,
+            ChanceCardEnum::AdvanceToReadingRailroad,
+            ChanceCardEnum::ChairmanOfTheBoard,
+            ChanceCardEnum::BuildingLoanMatures,
+        ]
+    }
+
+    pub fn get_action(&self) -> CardAction {
+        match self {
+            ChanceCardEnum::AdvanceToGo => CardAction::AdvanceTo(LocationEnum::Go),
+            ChanceCardEnum::AdvanceToStCharlesPlace => CardAction::AdvanceTo(LocationEnum::StCharlesPlace),
+            ChanceCardEnum::AdvanceToBoardwalk => CardAction::AdvanceTo(LocationEnum::Boardwalk),
+            ChanceCardEnum::AdvanceToNearestUtility => CardAction::AdvanceToNearest(LocationTypeEnum::Utility),
+            ChanceCardEnum::AdvanceToNearestRailroad => CardAction::AdvanceToNearest(LocationTypeEnum::Railroad),
+            ChanceCardEnum::AdvanceToIllinoisAvenue => CardAction::AdvanceTo(LocationEnum::IllinoisAvenue),
+            ChanceCardEnum::AdvanceToReadingRailroad => CardAction::AdvanceTo(LocationEnum::ReadingRailroad),
+            ChanceCardEnum::GoBackThreeSpaces => CardAction::GoBack(3),
+            ChanceCardEnum::GoToJail => CardAction::GoToJail,
+            ChanceCardEnum::GetOutOfJailFree => CardAction::GetOutOfJailFree,
+            ChanceCardEnum::BankPaysDividend => CardAction::Collect(50),
+            ChanceCardEnum::GeneralRepairs => CardAction::Repairs { per_house: 25, per_hotel: 100 },
+            ChanceCardEnum::SpeedingFine => CardAction::Pay(15),
+            ChanceCardEnum::ChairmanOfTheBoard => CardAction::PayEachPlayer(50),
+            ChanceCardEnum::BuildingLoanMatures => CardAction::Collect(150),
+        }
+    }
+}
+
+impl CommunityChestCardEnum {
+    pub fn get_deck() -> Vec<CommunityChestCardEnum> {
+        vec![
+            CommunityChestCardEnum::AdvanceToGo,
+            CommunityChestCardEnum::BankError,
+            CommunityChestCardEnum::DoctorsFee,
+            CommunityChestCardEnum::SaleOfStock,
+            CommunityChestCardEnum::GetOutOfJailFree,
+            CommunityChestCardEnum::GoToJail,
+            CommunityChestCardEnum::HolidayFundMatures,
+            CommunityChestCardEnum::IncomeTaxRefund,
+            CommunityChestCardEnum::Birthday,
+            CommunityChestCardEnum::LifeInsuranceMatures,
+            CommunityChestCardEnum::HospitalFees,
+            CommunityChestCardEnum::SchoolFees,
+            CommunityChestCardEnum::ConsultancyFee,
+            CommunityChestCardEnum::StreetRepairs,
+            CommunityChestCardEnum::BeautyContest,
+            CommunityChestCardEnum::Inheritance,
+        ]
+    }
+
+    pub fn get_action(&self) -> CardAction {
+        match self {
+            CommunityChestCardEnum::AdvanceToGo => CardAction::AdvanceTo(LocationEnum::Go),
+            CommunityChestCardEnum::GoToJail => CardAction::GoToJail,
+            CommunityChestCardEnum::GetOutOfJailFree => CardAction::GetOutOfJailFree,
+            CommunityChestCardEnum::BankError => CardAction::Collect(200),
+            CommunityChestCardEnum::DoctorsFee => CardAction::Pay(50),
+            CommunityChestCardEnum::SaleOfStock => CardAction::Collect(50),
+            CommunityChestCardEnum::HolidayFundMatures => CardAction::Collect(100),
+            CommunityChestCardEnum::IncomeTaxRefund => CardAction::Collect(20),
+            CommunityChestCardEnum::Birthday => CardAction::CollectFromEachPlayer(10),
+            CommunityChestCardEnum::LifeInsuranceMatures => CardAction::Collect(100),
+            CommunityChestCardEnum::HospitalFees => CardAction::Pay(100),
+            CommunityChestCardEnum::SchoolFees => CardAction::Pay(50),
+            CommunityChestCardEnum::ConsultancyFee => CardAction::Collect(25),
+            CommunityChestCardEnum::StreetRepairs => CardAction::Repairs { per_house: 40, per_hotel: 115 },
+            CommunityChestCardEnum::BeautyContest => CardAction::Collect(10),
+            CommunityChestCardEnum::Inheritance => CardAction::Collect(100),
+        }
+    }
+}
+
+/// Cards are drawn from the top and go back on the bottom once resolved.
+pub struct Deck<T: Copy> {
+    pub cards: VecDeque<T>,
+}
+
+impl<T: Copy> Deck<T> {
+    pub fn new(cards: Vec<T>) -> Self {
+        Self {
+            cards: cards.into_iter().collect()
+        }
+    }
+
+    pub fn new_shuffled(mut cards: Vec<T>) -> Self {
+        cards.shuffle(&mut rand::thread_rng());
+        Deck::new(cards)
+    }
+
+    /// Fisher-Yates shuffle driven by `rng`, so a seeded game always deals the same deck.
+    pub fn new_seeded(mut cards: Vec<T>, rng: &mut Pcg32) -> Self {
+        for i in (1..cards.len()).rev() {
+            let j = uniform_below(rng, i as u32 + 1) as usize;
+            cards.swap(i, j);
+        }
+        Deck::new(cards)
+    }
+
+    pub fn draw(&mut self) -> Option<T> {
+        self.cards.pop_front()
+    }
+
+    pub fn put_on_bottom(&mut self, card: T) {
+        self.cards.push_back(card);
+    }
+}
+
+impl BoardState {
+    pub fn draw_chance_card(&mut self, roll: &DiceRoll) -> Option<ChanceCardEnum> {
+        let card = self.chance_deck.draw()?;
+        if card != ChanceCardEnum::GetOutOfJailFree {
+            self.chance_deck.put_on_bottom(card);
+        }
+        self.emit(GameEvent::ChanceCardDrawn { player_number: self.player_turn, card });
+        self.apply_card_action(DeckEnum::Chance, card.get_action(), roll);
+        Some(card)
+    }
+
+    pub fn draw_community_chest_card(&mut self, roll: &DiceRoll) -> Option<CommunityChestCardEnum> {
+        let card = self.community_chest_deck.draw()?;
+        if card != CommunityChestCardEnum::GetOutOfJailFree {
+            self.community_chest_deck.put_on_bottom(card);
+        }
+        self.emit(GameEvent::CommunityChestCardDrawn { player_number: self.player_turn, card });
+        self.apply_card_action(DeckEnum::CommunityChest, card.get_action(), roll);
+        Some(card)
+    }
+
+    /// Puts a used Get Out of Jail Free card back under the deck it came from.
+    pub fn return_get_out_of_jail_free_card(&mut self, deck: DeckEnum) {
+        match deck {
+            DeckEnum::Chance => self.chance_deck.put_on_bottom(ChanceCardEnum::GetOutOfJailFree),
+            DeckEnum::CommunityChest => self.community_chest_deck.put_on_bottom(CommunityChestCardEnum::GetOutOfJailFree),
+        }
+    }
+
+    fn apply_card_action(&mut self, deck: DeckEnum, action: CardAction, roll: &DiceRoll) {
+        let player_number = self.player_turn;
+        match action {
+            CardAction::AdvanceTo(location) => {
+                let rules = self.rules.clone();
+                let player = self.get_current_player_by_number(player_number);
+                let last_location = player.current_location;
+                player.advance_to(location, &rules);
+                self.record_move(player_number, last_location);
+                self.resolve_landing(location, roll);
+            },
+            CardAction::AdvanceToNearest(location_type) => {
+                let location = self.find_nearest(location_type);
+                let rules = self.rules.clone();
+                let player = self.get_current_player_by_number(player_number);
+                let last_location = player.current_location;
+                player.advance_to(location, &rules);
+                self.record_move(player_number, last_location);
+                self.resolve_nearest_landing(location, location_type);
+            },
+            CardAction::GoBack(spaces) => {
+                let player = self.get_current_player_by_number(player_number);
+                let last_location = player.current_location;
+                let location = last_location.retreat(spaces);
+                player.current_location = location;
+                self.emit(GameEvent::Moved { player_number, from: last_location, to: location });
+                self.resolve_landing(location, roll);
+            },
+            CardAction::GoToJail => {
+                self.get_current_player_by_number(player_number).send_to_jail();
+                self.emit(GameEvent::SentToJail { player_number });
+            },
+            CardAction::GetOutOfJailFree => self.get_current_player_by_number(player_number).get_out_of_jail_free_cards.push(deck),
+            CardAction::Collect(amount) => self.get_current_player_by_number(player_number).cash += amount,
+            CardAction::Pay(amount) => self.pay_fine(player_number, amount),
+            CardAction::Repairs { per_house, per_hotel } => {
+                let (houses, hotels) = self.properties.count_buildings(player_number);
+                self.pay_fine(player_number, houses * per_house + hotels * per_hotel);
+            },
+            CardAction::PayEachPlayer(amount) => {
+                for other in self.get_other_player_numbers(player_number) {
+                    self.transfer_cash(player_number, other, amount);
+                }
+            },
+            CardAction::CollectFromEachPlayer(amount) => {
+                for other in self.get_other_player_numbers(player_number) {
+                    self.transfer_cash(other, player_number, amount);
+                }
+            },
+        }
+    }
+
+    fn find_nearest(&self, location_type: LocationTypeEnum) -> LocationEnum {
+        let start = self.players[&self.player_turn].current_location;
+        (1..=BOARD_SIZE)
+            .map(|offset| start.advance(offset))
+            .find(|location| location.get_location_type() == location_type)
+            .expect("Every board has railroads and utilities!")
+    }
+
+    // Railroads charge twice the usual rent and utilities ten times a fresh roll.
+    fn resolve_nearest_landing(&mut self, location: LocationEnum, location_type: LocationTypeEnum) {
+        match self.properties.get_property_ref(location).owned_by_player_number {
+            None => self.offer_purchase(self.player_turn, location),
+            Some(owner) if owner != self.player_turn => {
+                let rent = if location_type == LocationTypeEnum::Utility {
+                    let roll = self.roll_engine.borrow_mut().roll();
+                    if self.properties.get_property_ref(location).is_mortgaged { 0 } else { roll.total() * NEAREST_UTILITY_MULTIPLIER }
+                } else {
+                    self.properties.get_rent(location, 0) * NEAREST_RAILROAD_MULTIPLIER
+                };
+                self.pay_rent(self.player_turn, owner, location, rent);
+            },
+            Some(_) => {}
+        }
+    }
+
+    fn get_other_player_numbers(&self, player_number: usize) -> Vec<usize> {
+        let mut others: Vec<usize> = self.players.keys()
+            .copied()
+            .filter(|other| *other != player_number && !self.players[other].is_bankrupt)
+            .collect();
+        others.sort();
+        others
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::{ChanceCardEnum, CommunityChestCardEnum, DeckEnum, Deck};
+    use crate::dice::DiceRoll;
+    use crate::events::GameEvent;
+    use crate::location::LocationEnum;
+    use crate::test_support::get_new_board;
+
+    #[test]
+    fn decks_have_sixteen_cards() {
+        assert_eq!(ChanceCardEnum::get_deck().len(), 16);
+        assert_eq!(CommunityChestCardEnum::get_deck().len(), 16);
+        let board = get_new_board(DiceRoll(2, 3), 2);
+        assert_eq!(board.chance_deck.cards.len(), 16);
+        assert_eq!(board.community_chest_deck.cards.len(), 16);
+    }
+
+    #[test]
+    fn cards_are_drawn_in_order_and_go_to_the_bottom() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.community_chest_deck = Deck::new(vec![CommunityChestCardEnum::BankError, CommunityChestCardEnum::DoctorsFee]);
+        assert_eq!(board.draw_community_chest_card(&DiceRoll(1, 2)), Some(CommunityChestCardEnum::BankError));
+        assert_eq!(board.draw_community_chest_card(&DiceRoll(1, 2)), Some(CommunityChestCardEnum::DoctorsFee));
+        assert_eq!(board.draw_community_chest_card(&DiceRoll(1, 2)), Some(CommunityChestCardEnum::BankError));
+        assert_eq!(board.players[&1].cash, 1500 + 200 - 50 + 200);
+    }
+
+    #[test]
+    fn landing_on_chance_draws_a_card() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::AdvanceToBoardwalk]);
+        board.get_current_player_by_number(1).current_location = LocationEnum::CommunityChest1;
+        board.buy_property(2, LocationEnum::Boardwalk).unwrap();
+        board.take_turn();
+        let player = &board.players[&1];
+        assert_eq!(player.current_location, LocationEnum::Boardwalk);
+        assert_eq!(player.cash, 1450);
+    }
+
+    #[test]
+    fn get_out_of_jail_free_card_leaves_the_deck_while_held() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::GetOutOfJailFree, ChanceCardEnum::SpeedingFine]);
+        board.draw_chance_card(&DiceRoll(1, 2));
+        assert_eq!(board.chance_deck.cards.len(), 1);
+        assert_eq!(board.players[&1].get_out_of_jail_free_cards, vec![DeckEnum::Chance]);
+        board.return_get_out_of_jail_free_card(DeckEnum::Chance);
+        assert_eq!(board.chance_deck.cards.len(), 2);
+    }
+
+    #[test]
+    fn advance_to_go_collects_salary() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::AdvanceToGo]);
+        board.get_current_player_by_number(1).current_location = LocationEnum::Chance3;
+        board.draw_chance_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].current_location, LocationEnum::Go);
+        assert_eq!(board.players[&1].cash, 1700);
+    }
+
+    #[test]
+    fn nearest_railroad_charges_double_rent_and_wraps_past_go() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.buy_property(2, LocationEnum::ReadingRailroad).unwrap();
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::AdvanceToNearestRailroad]);
+        board.get_current_player_by_number(1).current_location = LocationEnum::Chance3;
+        board.draw_chance_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].current_location, LocationEnum::ReadingRailroad);
+        assert_eq!(board.players[&1].cash, 1500 + 200 - 50);
+    }
+
+    #[test]
+    fn nearest_utility_charges_ten_times_a_fresh_roll() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.buy_property(2, LocationEnum::ElectricCompany).unwrap();
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::AdvanceToNearestUtility]);
+        board.get_current_player_by_number(1).current_location = LocationEnum::Chance1;
+        board.draw_chance_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].current_location, LocationEnum::ElectricCompany);
+        assert_eq!(board.players[&1].cash, 1450);
+    }
+
+    #[test]
+    fn go_back_three_spaces_does_not_pay_salary() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::GoBackThreeSpaces]);
+        board.get_current_player_by_number(1).current_location = LocationEnum::Chance2;
+        board.draw_chance_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].current_location, LocationEnum::NewYorkAvenue);
+        assert_eq!(board.players[&1].cash, 1300);
+    }
+
+    #[test]
+    fn repairs_charge_per_house_and_hotel() {
+        let mut board = get_new_board(DiceRoll(2, 3), 2);
+        board.properties.get_property(LocationEnum::ParkPlace).houses = 3;
+        board.properties.get_property(LocationEnum::ParkPlace).owned_by_player_number = Some(1);
+        board.properties.get_property(LocationEnum::Boardwalk).hotels = 1;
+        board.properties.get_property(LocationEnum::Boardwalk).owned_by_player_number = Some(1);
+        board.community_chest_deck = Deck::new(vec![CommunityChestCardEnum::StreetRepairs]);
+        board.draw_community_chest_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].cash, 1500 - 120 - 115);
+    }
+
+    #[test]
+    fn paying_and_collecting_from_each_player() {
+        let mut board = get_new_board(DiceRoll(2, 3), 3);
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::ChairmanOfTheBoard]);
+        board.community_chest_deck = Deck::new(vec![CommunityChestCardEnum::Birthday]);
+        board.draw_chance_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].cash, 1400);
+        assert_eq!(board.players[&2].cash, 1550);
+        board.draw_community_chest_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].cash, 1420);
+        assert_eq!(board.players[&3].cash, 1540);
+    }
+
+    #[test]
+    fn bankrupt_players_neither_pay_nor_collect() {
+        let mut board = get_new_board(DiceRoll(2, 3), 3);
+        board.players.get_mut(&3).unwrap().cash = 0;
+        board.players.get_mut(&3).unwrap().is_bankrupt = true;
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::ChairmanOfTheBoard]);
+        board.community_chest_deck = Deck::new(vec![CommunityChestCardEnum::Birthday]);
+        board.draw_chance_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].cash, 1450);
+        assert_eq!(board.players[&3].cash, 0);
+        board.draw_community_chest_card(&DiceRoll(1, 2));
+        assert_eq!(board.players[&1].cash, 1460);
+        assert_eq!(board.players[&2].cash, 1540);
+        assert_eq!(board.players[&3].cash, 0);
+        assert!(!board.get_events().iter().any(|event| matches!(event, GameEvent::Bankrupt { .. })));
+    }
+}
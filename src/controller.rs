@@ -0,0 +1,268 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use serde::{Deserialize, Serialize};
+use crate::auction::{AuctionBidder, AuctionResult, AuctionStyleEnum, MINIMUM_BID_INCREMENT};
+use crate::board_state::BoardState;
+use crate::error::GameError;
+use crate::events::GameEvent;
+use crate::jail::JailOptionEnum;
+use crate::location::LocationEnum;
+use crate::trade::TradeOffer;
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub enum TurnActionEnum {
+    BuyHouse(LocationEnum),
+    BuyHotel(LocationEnum),
+    SellHouse(LocationEnum),
+    SellHotel(LocationEnum),
+    Mortgage(LocationEnum),
+    Unmortgage(LocationEnum),
+    ProposeTrade(TradeOffer),
+}
+
+/// Makes a player's decisions. `BoardState` asks the controller at every choice so humans,
+/// AIs and scripted test doubles all plug in the same way. Each default keeps the behaviour
+/// of a player with no controller.
+pub trait PlayerController {
+    /// Whether to buy the unowned `location` at its printed price. Declined deeds are auctioned.
+    fn should_buy(&mut self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        board.players[&player_number].cash >= board.properties.get_property_ref(location).cost
+    }
+
+    /// The bid to make in an auction while `highest_bid` stands, or None to drop out.
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        let bid = highest_bid + MINIMUM_BID_INCREMENT;
+        let affordable = bid <= board.players[&player_number].cash;
+        if affordable && bid <= board.properties.get_property_ref(location).cost { Some(bid) } else { None }
+    }
+
+    /// How to try leaving jail before rolling. `options` always includes rolling for doubles.
+    fn choose_jail_option(&mut self, _board: &BoardState, _player_number: usize, _options: &[JailOptionEnum]) -> JailOptionEnum {
+        JailOptionEnum::RollForDoubles
+    }
+
+    /// Where to go after rolling triples with the speed die. Any square may be chosen.
+    fn choose_destination(&mut self, board: &BoardState, player_number: usize) -> LocationEnum {
+        let location = board.players[&player_number].current_location;
+        board.get_next_unowned_deed(location).unwrap_or(LocationEnum::Go)
+    }
+
+    /// How many spaces to move when the speed die shows the Bus, picked from `options`.
+    fn choose_bus_move(&mut self, _board: &BoardState, _player_number: usize, options: &[i32]) -> i32 {
+        options.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Building, mortgaging and trade proposals to carry out, in order, once the player has moved.
+    fn plan_turn_actions(&mut self, _board: &BoardState, _player_number: usize) -> Vec<TurnActionEnum> {
+        Vec::new()
+    }
+
+    /// Whether to accept `offer`, made to `player_number` by another player.
+    fn respond_to_trade(&mut self, _board: &BoardState, _player_number: usize, _offer: &TradeOffer) -> bool {
+        false
+    }
+}
+
+pub struct DefaultController;
+impl PlayerController for DefaultController {}
+
+/// Routes each auction bid to the bidding player's controller.
+struct ControllerBidder {
+    controllers: HashMap<usize, Rc<RefCell<dyn PlayerController>>>,
+}
+
+impl AuctionBidder for ControllerBidder {
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        self.controllers[&player_number].borrow_mut().bid(board, player_number, location, highest_bid)
+    }
+}
+
+impl BoardState {
+    pub fn set_player_controller(&mut self, player_number: usize, controller: Rc<RefCell<dyn PlayerController>>) {
+        self.controllers.insert(player_number, controller);
+    }
+
+    pub fn get_player_controller(&self, player_number: usize) -> Rc<RefCell<dyn PlayerController>> {
+        match self.controllers.get(&player_number) {
+            Some(controller) => controller.clone(),
+            None => Rc::new(RefCell::new(DefaultController)),
+        }
+    }
+
+    /// Carries out one of `player_number`'s turn actions. Trades only go ahead if the other
+    /// player's controller accepts them.
+    pub fn perform_turn_action(&mut self, player_number: usize, action: &TurnActionEnum) -> Result<(), GameError> {
+        match action {
+            TurnActionEnum::BuyHouse(location) => self.buy_house(player_number, *location),
+            TurnActionEnum::BuyHotel(location) => self.buy_hotel(player_number, *location),
+            TurnActionEnum::SellHouse(location) => self.sell_house(player_number, *location),
+            TurnActionEnum::SellHotel(location) => self.sell_hotel(player_number, *location),
+            TurnActionEnum::Mortgage(location) => self.mortgage_property(player_number, *location),
+            TurnActionEnum::Unmortgage(location) => self.unmortgage_property(player_number, *location),
+            TurnActionEnum::ProposeTrade(offer) => {
+                if offer.from_player_number != player_number {
+                    return Err(GameError::InvalidTrade);
+                }
+                self.validate_trade(offer)?;
+                let controller = self.get_player_controller(offer.to_player_number);
+                let accepted = controller.borrow_mut().respond_to_trade(self, offer.to_player_number, offer);
+                if !accepted {
+                    return Err(GameError::TradeDeclined);
+                }
+                self.accept_trade(offer)
+            },
+        }
+    }
+
+    pub(crate) fn auction_to_controllers(&mut self, location: LocationEnum) -> Option<AuctionResult> {
+        let controllers = self.players.keys()
+            .map(|player_number| (*player_number, self.get_player_controller(*player_number)))
+            .collect();
+        self.auction_property(location, AuctionStyleEnum::OpenAscending, &mut ControllerBidder { controllers })
+    }
+
+    pub(crate) fn offer_jail_options(&mut self, player_number: usize) {
+        let options = self.get_jail_options(player_number);
+        if options.len() < 2 {
+            return;
+        }
+
+        let controller = self.get_player_controller(player_number);
+        let choice = controller.borrow_mut().choose_jail_option(self, player_number, &options);
+        let result = match choice {
+            JailOptionEnum::PayFine => self.pay_jail_fine(player_number),
+            JailOptionEnum::UseGetOutOfJailFreeCard => self.use_get_out_of_jail_free_card(player_number),
+            JailOptionEnum::RollForDoubles => Ok(()),
+        };
+        if let Err(error) = result {
+            self.emit(GameEvent::ActionRejected { player_number, error });
+        }
+    }
+
+    // Actions the rules do not allow are skipped and reported as rejected.
+    pub(crate) fn run_turn_actions(&mut self, player_number: usize) {
+        let controller = self.get_player_controller(player_number);
+        let actions = controller.borrow_mut().plan_turn_actions(self, player_number);
+        for action in actions {
+            if let Err(error) = self.perform_turn_action(player_number, &action) {
+                self.emit(GameEvent::ActionRejected { player_number, error });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::BoardState;
+    use crate::controller::{PlayerController, TurnActionEnum};
+    use crate::dice::DiceRoll;
+    use crate::error::GameError;
+    use crate::events::GameEvent;
+    use crate::jail::JailOptionEnum;
+    use crate::location::LocationEnum;
+    use crate::test_support::get_new_board;
+    use crate::trade::{TradeOffer, TradeSide};
+
+    struct ScriptedController {
+        buys: bool,
+        jail_option: JailOptionEnum,
+        actions: Vec<TurnActionEnum>,
+        accepts_trades: bool,
+    }
+
+    impl ScriptedController {
+        fn new() -> Self {
+            Self { buys: true, jail_option: JailOptionEnum::RollForDoubles, actions: Vec::new(), accepts_trades: false }
+        }
+    }
+
+    impl PlayerController for ScriptedController {
+        fn should_buy(&mut self, _: &BoardState, _: usize, _: LocationEnum) -> bool {
+            self.buys
+        }
+
+        fn choose_jail_option(&mut self, _: &BoardState, _: usize, _: &[JailOptionEnum]) -> JailOptionEnum {
+            self.jail_option
+        }
+
+        fn plan_turn_actions(&mut self, _: &BoardState, _: usize) -> Vec<TurnActionEnum> {
+            self.actions.drain(..).collect()
+        }
+
+        fn respond_to_trade(&mut self, _: &BoardState, _: usize, _: &TradeOffer) -> bool {
+            self.accepts_trades
+        }
+    }
+
+    #[test]
+    fn declining_to_buy_sends_the_property_to_auction() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        let mut controller = ScriptedController::new();
+        controller.buys = false;
+        board.set_player_controller(1, Rc::new(RefCell::new(controller)));
+        board.take_turn();
+        let property = board.properties.get_property(LocationEnum::BalticAvenue);
+        assert_eq!(property.owned_by_player_number, Some(2));
+        assert_eq!(board.players[&2].cash, 1440);
+    }
+
+    #[test]
+    fn controller_can_pay_to_leave_jail_before_rolling() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        let mut controller = ScriptedController::new();
+        controller.jail_option = JailOptionEnum::PayFine;
+        board.set_player_controller(1, Rc::new(RefCell::new(controller)));
+        board.get_current_player_by_number(1).send_to_jail();
+        board.take_turn();
+        let player = &board.players[&1];
+        assert!(!player.is_in_jail);
+        assert_eq!(player.current_location, LocationEnum::StateAvenue);
+        assert_eq!(player.cash, 1500 - 50 - 140);
+    }
+
+    #[test]
+    fn controller_builds_after_moving() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::MediterraneanAvenue).unwrap();
+        let mut controller = ScriptedController::new();
+        controller.actions = vec![
+            TurnActionEnum::BuyHouse(LocationEnum::MediterraneanAvenue),
+            TurnActionEnum::BuyHouse(LocationEnum::BalticAvenue),
+        ];
+        board.set_player_controller(1, Rc::new(RefCell::new(controller)));
+        board.take_turn();
+        assert_eq!(board.properties.get_property(LocationEnum::MediterraneanAvenue).houses, 1);
+        assert_eq!(board.properties.get_property(LocationEnum::BalticAvenue).houses, 1);
+    }
+
+    #[test]
+    fn rejected_actions_are_reported() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        let mut controller = ScriptedController::new();
+        controller.actions = vec![TurnActionEnum::BuyHouse(LocationEnum::Boardwalk)];
+        board.set_player_controller(1, Rc::new(RefCell::new(controller)));
+        board.take_turn();
+        assert_eq!(board.properties.get_property(LocationEnum::Boardwalk).houses, 0);
+        assert!(board.get_events().contains(&GameEvent::ActionRejected { player_number: 1, error: GameError::NotOwner(LocationEnum::Boardwalk) }));
+    }
+
+    #[test]
+    fn trades_need_the_other_players_consent() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(2, LocationEnum::Boardwalk).unwrap();
+        let offer = TradeOffer::new(1, 2,
+            TradeSide { cash: 500, ..Default::default() },
+            TradeSide { properties: vec![LocationEnum::Boardwalk], ..Default::default() });
+        let action = TurnActionEnum::ProposeTrade(offer);
+        assert_eq!(board.perform_turn_action(1, &action), Err(GameError::TradeDeclined));
+
+        let mut controller = ScriptedController::new();
+        controller.accepts_trades = true;
+        board.set_player_controller(2, Rc::new(RefCell::new(controller)));
+        board.perform_turn_action(1, &action).unwrap();
+        assert_eq!(board.properties.get_property(LocationEnum::Boardwalk).owned_by_player_number, Some(1));
+    }
+}
@@ -1,24 +1,218 @@
-use rand::Rng;
+use rand::{Rng, RngCore};
+use rand_pcg::Pcg32;
+use serde::{Deserialize, Serialize};
+
+/// PCG stream used for dice rolls. Other seeded randomness draws from its own stream of the same seed.
+pub const DICE_STREAM: u64 = 0xda3e39cb94b95bdb;
 
 pub trait RollEngine {
-    fn roll(&self) -> DiceRoll;
+    fn roll(&mut self) -> DiceRoll;
+
+    /// Rolls the speed die, for games played with one. Engines without a speed die return None
+    /// and the turn goes ahead on the white dice alone.
+    fn roll_speed_die(&mut self) -> Option<SpeedDieFace> {
+        None
+    }
+
+    /// The dice as they stand now, for engines whose state can be saved and restored.
+    fn save_state(&self) -> Option<SeededDice> {
+        None
+    }
 }
 
-#[derive(Clone)]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub struct DiceRoll(pub i32, pub i32);
 impl DiceRoll {
     pub fn is_doubles(&self) -> bool {
         self.0 == self.1
     }
+
+    pub fn total(&self) -> i32 {
+        self.0 + self.1
+    }
+}
+
+/// The Mega Edition speed die. Mr. Monopoly is printed on two of its six faces.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SpeedDieFace {
+    One,
+    Two,
+    Three,
+    MrMonopoly,
+    Bus,
+}
+
+impl SpeedDieFace {
+    /// The face showing for a roll of 1 to 6.
+    pub fn from_roll(value: i32) -> Self {
+        match value {
+            1 => SpeedDieFace::One,
+            2 => SpeedDieFace::Two,
+            3 => SpeedDieFace::Three,
+            6 => SpeedDieFace::Bus,
+            _ => SpeedDieFace::MrMonopoly,
+        }
+    }
+
+    /// The number shown, for the numbered faces.
+    pub fn get_value(&self) -> Option<i32> {
+        match self {
+            SpeedDieFace::One => Some(1),
+            SpeedDieFace::Two => Some(2),
+            SpeedDieFace::Three => Some(3),
+            SpeedDieFace::MrMonopoly | SpeedDieFace::Bus => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            SpeedDieFace::One => "1",
+            SpeedDieFace::Two => "2",
+            SpeedDieFace::Three => "3",
+            SpeedDieFace::MrMonopoly => "Mr. Monopoly",
+            SpeedDieFace::Bus => "the Bus",
+        }
+    }
+}
+
+/// Everything rolled at the start of a move: the white dice, plus the speed die when the game uses it.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Roll {
+    pub dice: DiceRoll,
+    pub speed_die: Option<SpeedDieFace>,
+}
+
+impl Roll {
+    /// All three dice show the same number.
+    pub fn is_triples(&self) -> bool {
+        self.dice.is_doubles() && self.speed_die.and_then(|face| face.get_value()) == Some(self.dice.0)
+    }
+
+    /// The spaces to move, counting a number on the speed die.
+    pub fn total(&self) -> i32 {
+        self.dice.total() + self.speed_die.and_then(|face| face.get_value()).unwrap_or(0)
+    }
+
+    /// The moves the Bus offers: either white die on its own, or both together.
+    pub fn get_bus_options(&self) -> Vec<i32> {
+        let mut options = vec![self.dice.0, self.dice.1, self.dice.total()];
+        options.dedup();
+        options
+    }
 }
 
 pub struct Dice;
 impl RollEngine for Dice {
-    fn roll(&self) -> DiceRoll {
+    fn roll(&mut self) -> DiceRoll {
         let mut rng = rand::thread_rng();
-        let first = rng.gen_range(1..6);
-        let second = rng.gen_range(1..6);
+        let first = rng.gen_range(1..=6);
+        let second = rng.gen_range(1..=6);
+
+        DiceRoll(first, second)
+    }
+
+    fn roll_speed_die(&mut self) -> Option<SpeedDieFace> {
+        Some(SpeedDieFace::from_roll(rand::thread_rng().gen_range(1..=6)))
+    }
+}
+
+/// Dice driven by a seeded PCG32, so the same seed always rolls the same game.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct SeededDice {
+    seed: u64,
+    rng: Pcg32,
+}
+
+impl SeededDice {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Pcg32::new(seed, DICE_STREAM),
+        }
+    }
 
-        return DiceRoll{ 0: first, 1: second }
+    pub fn get_seed(&self) -> u64 {
+        self.seed
     }
-}
\ No newline at end of file
+}
+
+impl RollEngine for SeededDice {
+    fn roll(&mut self) -> DiceRoll {
+        let first = uniform_below(&mut self.rng, 6) as i32 + 1;
+        let second = uniform_below(&mut self.rng, 6) as i32 + 1;
+
+        DiceRoll(first, second)
+    }
+
+    fn roll_speed_die(&mut self) -> Option<SpeedDieFace> {
+        Some(SpeedDieFace::from_roll(uniform_below(&mut self.rng, 6) as i32 + 1))
+    }
+
+    fn save_state(&self) -> Option<SeededDice> {
+        Some(self.clone())
+    }
+}
+
+/// A uniform value in `0..bound`. Rejection sampling on the raw 32-bit output keeps results
+/// free of modulo bias and independent of how `rand` maps ranges between releases.
+pub(crate) fn uniform_below(rng: &mut Pcg32, bound: u32) -> u32 {
+    let zone = u32::MAX - u32::MAX % bound;
+    loop {
+        let value = rng.next_u32();
+        if value < zone {
+            return value % bound;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dice::{Dice, DiceRoll, Roll, RollEngine, SeededDice, SpeedDieFace};
+
+    #[test]
+    fn dice_roll_every_face() {
+        let mut dice = Dice;
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let roll = dice.roll();
+            seen[roll.0 as usize - 1] = true;
+            seen[roll.1 as usize - 1] = true;
+        }
+        assert!(seen.iter().all(|face| *face));
+    }
+
+    #[test]
+    fn same_seed_rolls_the_same_sequence() {
+        let mut first = SeededDice::new(42);
+        let mut second = SeededDice::new(42);
+        let rolls: Vec<(i32, i32)> = (0..100).map(|_| { let roll = first.roll(); (roll.0, roll.1) }).collect();
+        let again: Vec<(i32, i32)> = (0..100).map(|_| { let roll = second.roll(); (roll.0, roll.1) }).collect();
+        assert_eq!(rolls, again);
+        assert!(rolls.iter().all(|(a, b)| (1..=6).contains(a) && (1..=6).contains(b)));
+        assert!(rolls.iter().any(|(a, b)| *a == 6 || *b == 6));
+    }
+
+    #[test]
+    fn seeded_rolls_are_stable_across_releases() {
+        let mut dice = SeededDice::new(2024);
+        let rolls: Vec<(i32, i32)> = (0..4).map(|_| { let roll = dice.roll(); (roll.0, roll.1) }).collect();
+        assert_eq!(rolls, vec![(6, 2), (4, 3), (3, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn speed_die_counts_toward_the_move() {
+        let roll = Roll { dice: DiceRoll(2, 2), speed_die: Some(SpeedDieFace::Two) };
+        assert!(roll.is_triples());
+        assert_eq!(roll.total(), 6);
+        assert_eq!(roll.get_bus_options(), vec![2, 4]);
+
+        let roll = Roll { dice: DiceRoll(2, 5), speed_die: Some(SpeedDieFace::MrMonopoly) };
+        assert!(!roll.is_triples());
+        assert_eq!(roll.total(), 7);
+        assert_eq!(roll.get_bus_options(), vec![2, 5, 7]);
+
+        let faces: Vec<SpeedDieFace> = (1..=6).map(SpeedDieFace::from_roll).collect();
+        assert_eq!(faces.iter().filter(|face| **face == SpeedDieFace::MrMonopoly).count(), 2);
+        assert!(SeededDice::new(7).roll_speed_die().is_some());
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+use crate::location::{ColorGroupEnum, LocationEnum};
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum GameError {
+    PropertyNotPurchasable(LocationEnum),
+    PropertyAlreadyOwned(LocationEnum),
+    InsufficientFunds { needed: i32, available: i32 },
+    NotOwner(LocationEnum),
+    NoMonopoly(ColorGroupEnum),
+    NotBuildable(LocationEnum),
+    UnevenBuilding(LocationEnum),
+    BuildingLimitReached(LocationEnum),
+    NoBuildings(LocationEnum),
+    HouseShortage,
+    HotelShortage,
+    Mortgaged(LocationEnum),
+    NotMortgaged(LocationEnum),
+    GroupHasBuildings(ColorGroupEnum),
+    NotInJail,
+    NoGetOutOfJailFreeCard,
+    InvalidTrade,
+    TradeDeclined,
+    InvalidLocation(usize),
+    InvalidSave(String),
+    InvalidRules(String),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::PropertyNotPurchasable(location) => write!(f, "{:?} cannot be purchased", location),
+            GameError::PropertyAlreadyOwned(location) => write!(f, "{:?} is already owned", location),
+            GameError::InsufficientFunds { needed, available } => write!(f, "needed ${} but only ${} is available", needed, available),
+            GameError::NotOwner(location) => write!(f, "{:?} is not owned by this player", location),
+            GameError::NoMonopoly(group) => write!(f, "the {:?} group is not a monopoly", group),
+            GameError::NotBuildable(location) => write!(f, "buildings cannot be placed on {:?}", location),
+            GameError::UnevenBuilding(location) => write!(f, "{:?} would break the even-build rule", location),
+            GameError::BuildingLimitReached(location) => write!(f, "{:?} cannot take another building", location),
+            GameError::NoBuildings(location) => write!(f, "{:?} has no buildings to sell", location),
+            GameError::HouseShortage => write!(f, "the bank has no houses left"),
+            GameError::HotelShortage => write!(f, "the bank has no hotels left"),
+            GameError::Mortgaged(location) => write!(f, "{:?} is mortgaged", location),
+            GameError::NotMortgaged(location) => write!(f, "{:?} is not mortgaged", location),
+            GameError::GroupHasBuildings(group) => write!(f, "the {:?} group still has buildings", group),
+            GameError::NotInJail => write!(f, "the player is not in jail"),
+            GameError::NoGetOutOfJailFreeCard => write!(f, "the player has no Get Out of Jail Free card"),
+            GameError::InvalidTrade => write!(f, "the trade is not valid"),
+            GameError::TradeDeclined => write!(f, "the trade was declined"),
+            GameError::InvalidLocation(number) => write!(f, "there is no square numbered {}", number),
+            GameError::InvalidSave(reason) => write!(f, "the saved game could not be loaded: {}", reason),
+            GameError::InvalidRules(reason) => write!(f, "the house rules could not be loaded: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::board_state::BoardState;
+use crate::cards::{ChanceCardEnum, CommunityChestCardEnum};
+use crate::dice::{DiceRoll, SpeedDieFace};
+use crate::error::GameError;
+use crate::jail::JailOptionEnum;
+use crate::location::LocationEnum;
+use crate::trade::TradeOffer;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum GameEvent {
+    TurnStarted { player_number: usize },
+    Rolled { player_number: usize, roll: DiceRoll },
+    SpeedDieRolled { player_number: usize, face: SpeedDieFace },
+    Moved { player_number: usize, from: LocationEnum, to: LocationEnum },
+    PassedGo { player_number: usize },
+    PropertyPurchased { player_number: usize, location: LocationEnum, price: i32 },
+    AuctionWon { player_number: usize, location: LocationEnum, price: i32 },
+    RentPaid { player_number: usize, owner: usize, location: LocationEnum, amount: i32 },
+    ChanceCardDrawn { player_number: usize, card: ChanceCardEnum },
+    CommunityChestCardDrawn { player_number: usize, card: CommunityChestCardEnum },
+    SentToJail { player_number: usize },
+    /// `how` is `PayFine` both for paying up front and for the forced fine after the last failed roll.
+    LeftJail { player_number: usize, how: JailOptionEnum },
+    HouseBuilt { player_number: usize, location: LocationEnum },
+    HotelBuilt { player_number: usize, location: LocationEnum },
+    HouseSold { player_number: usize, location: LocationEnum },
+    HotelSold { player_number: usize, location: LocationEnum },
+    Mortgaged { player_number: usize, location: LocationEnum },
+    Unmortgaged { player_number: usize, location: LocationEnum },
+    TradeCompleted(TradeOffer),
+    Bankrupt { player_number: usize, creditor: Option<usize> },
+    FreeParkingCollected { player_number: usize, amount: i32 },
+    /// A jail option or turn action the controller chose that the rules did not allow.
+    ActionRejected { player_number: usize, error: GameError },
+}
+
+/// Receives every event as the board emits it.
+pub trait EventSubscriber {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+impl BoardState {
+    pub fn subscribe(&mut self, subscriber: Rc<RefCell<dyn EventSubscriber>>) {
+        self.subscribers.push(subscriber);
+    }
+
+    /// Everything that has happened this game, oldest first.
+    pub fn get_events(&self) -> &[GameEvent] {
+        &self.events
+    }
+
+    pub(crate) fn emit(&mut self, event: GameEvent) {
+        for subscriber in &self.subscribers {
+            subscriber.borrow_mut().on_event(&event);
+        }
+        self.events.push(event);
+    }
+
+    /// Records a forward move from `from` to wherever `player_number` now stands.
+    pub(crate) fn record_move(&mut self, player_number: usize, from: LocationEnum) {
+        let to = self.players[&player_number].current_location;
+        if to == from {
+            return;
+        }
+
+        self.emit(GameEvent::Moved { player_number, from, to });
+        if to < from {
+            self.emit(GameEvent::PassedGo { player_number });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::cards::{CommunityChestCardEnum, Deck};
+    use crate::dice::DiceRoll;
+    use crate::events::{EventSubscriber, GameEvent};
+    use crate::location::LocationEnum;
+    use crate::test_support::{get_new_board, FixedRollEngine};
+
+    struct Recorder(Vec<GameEvent>);
+    impl EventSubscriber for Recorder {
+        fn on_event(&mut self, event: &GameEvent) {
+            self.0.push(event.clone());
+        }
+    }
+
+    #[test]
+    fn turn_emits_roll_move_and_purchase() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        let recorder = Rc::new(RefCell::new(Recorder(Vec::new())));
+        board.subscribe(recorder.clone());
+        board.take_turn();
+        let expected = vec![
+            GameEvent::TurnStarted { player_number: 1 },
+            GameEvent::Rolled { player_number: 1, roll: DiceRoll(1, 2) },
+            GameEvent::Moved { player_number: 1, from: LocationEnum::Go, to: LocationEnum::BalticAvenue },
+            GameEvent::PropertyPurchased { player_number: 1, location: LocationEnum::BalticAvenue, price: 60 },
+        ];
+        assert_eq!(board.get_events(), expected.as_slice());
+        assert_eq!(recorder.borrow().0, expected);
+    }
+
+    #[test]
+    fn rent_go_and_cards_are_logged() {
+        let mut board = get_new_board(DiceRoll(2, 4), 2);
+        board.buy_property(2, LocationEnum::ReadingRailroad).unwrap();
+        board.get_current_player_by_number(1).current_location = LocationEnum::Boardwalk;
+        board.community_chest_deck = Deck::new(vec![CommunityChestCardEnum::GoToJail]);
+        board.take_turn();
+        assert!(board.get_events().contains(&GameEvent::PassedGo { player_number: 1 }));
+        assert!(board.get_events().contains(&GameEvent::RentPaid { player_number: 1, owner: 2, location: LocationEnum::ReadingRailroad, amount: 25 }));
+
+        board.player_turn = 1;
+        board.get_current_player_by_number(1).current_location = LocationEnum::Go;
+        board.roll_engine = Rc::new(RefCell::new(FixedRollEngine(DiceRoll(1, 1))));
+        board.take_turn();
+        let events = board.get_events();
+        assert!(events.ends_with(&[
+            GameEvent::CommunityChestCardDrawn { player_number: 1, card: CommunityChestCardEnum::GoToJail },
+            GameEvent::SentToJail { player_number: 1 },
+        ]));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::board_state::BoardState;
+use crate::events::GameEvent;
+
+impl BoardState {
+    /// Charges a tax or card fine. Under the Free Parking jackpot rule it goes into the pot,
+    /// unless the player goes bankrupt trying to pay.
+    pub(crate) fn pay_fine(&mut self, player_number: usize, amount: i32) {
+        self.charge(player_number, None, amount);
+        if self.rules.free_parking_jackpot && amount > 0 && !self.players[&player_number].is_bankrupt {
+            self.free_parking_pot += amount;
+        }
+    }
+
+    /// Hands the pot to `player_number` and starts it again from the seed.
+    pub fn collect_free_parking(&mut self, player_number: usize) {
+        if !self.rules.free_parking_jackpot {
+            return;
+        }
+
+        let amount = self.free_parking_pot;
+        self.free_parking_pot = self.rules.free_parking_seed;
+        self.get_current_player_by_number(player_number).cash += amount;
+        self.emit(GameEvent::FreeParkingCollected { player_number, amount });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::BoardState;
+    use crate::cards::{ChanceCardEnum, Deck};
+    use crate::dice::DiceRoll;
+    use crate::events::GameEvent;
+    use crate::location::LocationEnum;
+    use crate::rules::GameRules;
+    use crate::test_support::{get_board_with_rules, FixedRollEngine};
+
+    fn jackpot_rules(seed: i32) -> GameRules {
+        GameRules { free_parking_jackpot: true, free_parking_seed: seed, ..GameRules::default() }
+    }
+
+    #[test]
+    fn taxes_and_card_fines_fill_the_pot() {
+        let mut board = get_board_with_rules(DiceRoll(1, 3), 2, jackpot_rules(0));
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::IncomeTax);
+        assert_eq!(board.free_parking_pot, 200);
+
+        board.chance_deck = Deck::new(vec![ChanceCardEnum::SpeedingFine]);
+        board.get_current_player_by_number(2).current_location = LocationEnum::Go;
+        board.roll_engine = Rc::new(RefCell::new(FixedRollEngine(DiceRoll(3, 4))));
+        board.take_turn();
+        assert_eq!(board.players[&2].current_location, LocationEnum::Chance1);
+        assert_eq!(board.free_parking_pot, 215);
+    }
+
+    #[test]
+    fn landing_on_free_parking_collects_the_pot_and_reseeds_it() {
+        let mut board = get_board_with_rules(DiceRoll(4, 6), 2, jackpot_rules(500));
+        assert_eq!(board.free_parking_pot, 500);
+        board.free_parking_pot = 740;
+        board.get_current_player_by_number(1).current_location = LocationEnum::Jail;
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::FreeParking);
+        assert_eq!(board.players[&1].cash, 1500 + 740);
+        assert_eq!(board.free_parking_pot, 500);
+        assert!(board.get_events().contains(&GameEvent::FreeParkingCollected { player_number: 1, amount: 740 }));
+
+        board.free_parking_pot = 650;
+        let loaded = BoardState::load_from_json(&board.save_to_json()).unwrap();
+        assert_eq!(loaded.free_parking_pot, 650);
+        assert!(loaded.rules.free_parking_jackpot);
+    }
+
+    #[test]
+    fn pot_stays_empty_without_the_rule() {
+        let mut board = get_board_with_rules(DiceRoll(1, 3), 2, GameRules { free_parking_seed: 500, ..GameRules::default() });
+        board.take_turn();
+        assert_eq!(board.players[&1].cash, 1300);
+        assert_eq!(board.free_parking_pot, 0);
+
+        board.get_current_player_by_number(2).current_location = LocationEnum::StJamesPlace;
+        board.take_turn();
+        assert_eq!(board.players[&2].current_location, LocationEnum::FreeParking);
+        assert_eq!(board.players[&2].cash, 1500);
+    }
+
+    #[test]
+    fn bankrupt_payers_add_nothing() {
+        let mut board = get_board_with_rules(DiceRoll(1, 3), 2, jackpot_rules(0));
+        board.get_current_player_by_number(1).cash = 100;
+        board.take_turn();
+        assert!(board.players[&1].is_bankrupt);
+        assert_eq!(board.free_parking_pot, 0);
+    }
+}
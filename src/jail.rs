@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use crate::board_state::BoardState;
+use crate::error::GameError;
+use crate::events::GameEvent;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum JailOptionEnum {
+    PayFine,
+    UseGetOutOfJailFreeCard,
+    RollForDoubles,
+}
+
+impl BoardState {
+    /// The ways `player_number` may try to leave jail before rolling this turn.
+    pub fn get_jail_options(&self, player_number: usize) -> Vec<JailOptionEnum> {
+        let player = &self.players[&player_number];
+        if !player.is_in_jail {
+            return Vec::new();
+        }
+
+        let mut options = Vec::new();
+        if player.cash >= self.rules.jail_fine {
+            options.push(JailOptionEnum::PayFine);
+        }
+        if !player.get_out_of_jail_free_cards.is_empty() {
+            options.push(JailOptionEnum::UseGetOutOfJailFreeCard);
+        }
+        options.push(JailOptionEnum::RollForDoubles);
+        options
+    }
+
+    /// Pays the fine before rolling. The player then rolls and moves as normal.
+    pub fn pay_jail_fine(&mut self, player_number: usize) -> Result<(), GameError> {
+        let fine = self.rules.jail_fine;
+        let player = self.get_current_player_by_number(player_number);
+        if !player.is_in_jail {
+            return Err(GameError::NotInJail);
+        }
+        if player.cash < fine {
+            return Err(GameError::InsufficientFunds { needed: fine, available: player.cash });
+        }
+
+        player.cash -= fine;
+        player.remove_from_jail();
+        self.emit(GameEvent::LeftJail { player_number, how: JailOptionEnum::PayFine });
+        Ok(())
+    }
+
+    /// Spends a held Get Out of Jail Free card, returning it to the bottom of its deck.
+    pub fn use_get_out_of_jail_free_card(&mut self, player_number: usize) -> Result<(), GameError> {
+        let player = self.get_current_player_by_number(player_number);
+        if !player.is_in_jail {
+            return Err(GameError::NotInJail);
+        }
+
+        let deck = player.get_out_of_jail_free_cards.pop().ok_or(GameError::NoGetOutOfJailFreeCard)?;
+        player.remove_from_jail();
+        self.return_get_out_of_jail_free_card(deck);
+        self.emit(GameEvent::LeftJail { player_number, how: JailOptionEnum::UseGetOutOfJailFreeCard });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::BoardState;
+    use crate::cards::{ChanceCardEnum, DeckEnum};
+    use crate::dice::DiceRoll;
+    use crate::error::GameError;
+    use crate::jail::JailOptionEnum;
+    use crate::test_support::get_new_board;
+
+    fn get_board_with_jailed_player() -> BoardState {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.get_current_player_by_number(1).send_to_jail();
+        board
+    }
+
+    #[test]
+    fn free_players_have_no_jail_options() {
+        let board = get_board_with_jailed_player();
+        assert!(board.get_jail_options(2).is_empty());
+    }
+
+    #[test]
+    fn jailed_player_options_depend_on_cash_and_cards() {
+        let mut board = get_board_with_jailed_player();
+        assert_eq!(board.get_jail_options(1), vec![JailOptionEnum::PayFine, JailOptionEnum::RollForDoubles]);
+
+        let player = board.get_current_player_by_number(1);
+        player.cash = 10;
+        player.get_out_of_jail_free_cards.push(DeckEnum::Chance);
+        assert_eq!(board.get_jail_options(1), vec![JailOptionEnum::UseGetOutOfJailFreeCard, JailOptionEnum::RollForDoubles]);
+    }
+
+    #[test]
+    fn paying_the_fine_leaves_jail() {
+        let mut board = get_board_with_jailed_player();
+        board.pay_jail_fine(1).unwrap();
+        let player = board.get_current_player_by_number(1);
+        assert!(!player.is_in_jail);
+        assert_eq!(player.cash, 1450);
+        assert_eq!(board.pay_jail_fine(1), Err(GameError::NotInJail));
+    }
+
+    #[test]
+    fn using_a_card_leaves_jail_and_returns_it_to_the_deck() {
+        let mut board = get_board_with_jailed_player();
+        assert_eq!(board.use_get_out_of_jail_free_card(1), Err(GameError::NoGetOutOfJailFreeCard));
+
+        board.chance_deck.cards.retain(|card| *card != ChanceCardEnum::GetOutOfJailFree);
+        board.get_current_player_by_number(1).get_out_of_jail_free_cards.push(DeckEnum::Chance);
+        board.use_get_out_of_jail_free_card(1).unwrap();
+        let player = board.get_current_player_by_number(1);
+        assert!(!player.is_in_jail);
+        assert!(player.get_out_of_jail_free_cards.is_empty());
+        assert_eq!(board.chance_deck.cards.back(), Some(&ChanceCardEnum::GetOutOfJailFree));
+    }
+}
@@ -0,0 +1,79 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+pub use crate::board_state::BoardState;
+use crate::dice::Dice;
+use crate::rules::GameRules;
+pub use crate::player::Player;
+
+pub mod analysis;
+pub mod auction;
+pub mod bankruptcy;
+pub mod board_state;
+pub mod building;
+pub mod player;
+pub mod render;
+pub mod replay;
+pub mod rules;
+pub mod save;
+pub mod simulation;
+pub mod location;
+pub mod cards;
+pub mod controller;
+pub mod dice;
+pub mod speed_die;
+pub mod square_action;
+pub mod strategy;
+pub mod terminal;
+pub mod trade;
+pub mod error;
+pub mod free_parking;
+pub mod events;
+pub mod jail;
+pub mod mortgage;
+#[cfg(test)]
+pub(crate) mod test_support;
+
+pub fn create_new_game(player_count: i32) -> BoardState {
+    let roll_engine = Rc::new(RefCell::new(Dice{}));
+    let mut board = BoardState::new(roll_engine, GameRules::default());
+    board.seat_new_players(player_count);
+    board
+}
+
+pub fn create_new_players(player_count: i32, rules: &GameRules) -> Vec<Player> {
+    Player::create_players(player_count, rules)
+}
+
+pub fn create_new_player(player_number: i32, rules: &GameRules) -> Player {
+    Player::new(player_number as usize, rules)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{create_new_game, create_new_player, create_new_players};
+
+    #[test]
+    fn can_create_new_game() {
+        let board = create_new_game(5);
+        assert_eq!(5, board.players.len());
+        assert_eq!(1, board.player_turn);
+    }
+
+    #[test]
+    fn can_add_players_to_board() {
+        let mut board = create_new_game(0);
+        assert_eq!(0, board.players.len());
+        let players = create_new_players(2, &board.rules);
+        board.add_players(players);
+        assert_eq!(2, board.players.len());
+    }
+
+    #[test]
+    fn can_add_player_to_board() {
+        let mut board = create_new_game(0);
+        assert_eq!(0, board.players.len());
+        let player = create_new_player(1, &board.rules);
+        board.add_player(player);
+        assert_eq!(1, board.players.len());
+    }
+}
@@ -1,8 +1,13 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use crate::Player;
+use std::rc::Rc;
+use serde::{Deserialize, Serialize};
+use crate::error::GameError;
+use crate::square_action::{chance_action, community_chest_action, deed_action, free_parking_action, go_to_jail_action, no_action, tax_action, SquareAction};
 
-#[derive(PartialEq, PartialOrd, Ord, Eq, Debug, Clone, Copy)]
+pub const BOARD_SIZE: i32 = 40;
+
+#[derive(PartialEq, PartialOrd, Ord, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum LocationEnum {
     Go = 1,
     MediterraneanAvenue = 2,
@@ -46,7 +51,7 @@ pub enum LocationEnum {
     Boardwalk = 40,
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum LocationTypeEnum {
     Go,
     Property,
@@ -59,6 +64,46 @@ pub enum LocationTypeEnum {
     Jail
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum ColorGroupEnum {
+    Brown,
+    LightBlue,
+    Pink,
+    Orange,
+    Red,
+    Yellow,
+    Green,
+    DarkBlue,
+}
+
+impl ColorGroupEnum {
+    pub fn all() -> [ColorGroupEnum; 8] {
+        [
+            ColorGroupEnum::Brown,
+            ColorGroupEnum::LightBlue,
+            ColorGroupEnum::Pink,
+            ColorGroupEnum::Orange,
+            ColorGroupEnum::Red,
+            ColorGroupEnum::Yellow,
+            ColorGroupEnum::Green,
+            ColorGroupEnum::DarkBlue,
+        ]
+    }
+
+    pub fn get_locations(&self) -> Vec<LocationEnum> {
+        match self {
+            ColorGroupEnum::Brown => vec![LocationEnum::MediterraneanAvenue, LocationEnum::BalticAvenue],
+            ColorGroupEnum::LightBlue => vec![LocationEnum::OrientalAvenue, LocationEnum::VermontAvenue, LocationEnum::ConnecticutAvenue],
+            ColorGroupEnum::Pink => vec![LocationEnum::StCharlesPlace, LocationEnum::StateAvenue, LocationEnum::VirginiaAvenue],
+            ColorGroupEnum::Orange => vec![LocationEnum::StJamesPlace, LocationEnum::TennesseeAvenue, LocationEnum::NewYorkAvenue],
+            ColorGroupEnum::Red => vec![LocationEnum::KentuckyAvenue, LocationEnum::IndianaAvenue, LocationEnum::IllinoisAvenue],
+            ColorGroupEnum::Yellow => vec![LocationEnum::AtlanticAvenue, LocationEnum::VentnorAvenue, LocationEnum::MarvinGardens],
+            ColorGroupEnum::Green => vec![LocationEnum::PacificAvenue, LocationEnum::NorthCarolinaAvenue, LocationEnum::PennsylvaniaAvenue],
+            ColorGroupEnum::DarkBlue => vec![LocationEnum::ParkPlace, LocationEnum::Boardwalk],
+        }
+    }
+}
+
 impl LocationEnum {
     pub fn get_location_type(&self) -> LocationTypeEnum {
         match self {
@@ -105,12 +150,83 @@ impl LocationEnum {
         }
     }
 
+    /// The name printed on the board.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            LocationEnum::Go => "Go",
+            LocationEnum::MediterraneanAvenue => "Mediterranean Avenue",
+            LocationEnum::CommunityChest1 => "Community Chest",
+            LocationEnum::BalticAvenue => "Baltic Avenue",
+            LocationEnum::IncomeTax => "Income Tax",
+            LocationEnum::ReadingRailroad => "Reading Railroad",
+            LocationEnum::OrientalAvenue => "Oriental Avenue",
+            LocationEnum::Chance1 => "Chance",
+            LocationEnum::VermontAvenue => "Vermont Avenue",
+            LocationEnum::ConnecticutAvenue => "Connecticut Avenue",
+            LocationEnum::Jail => "Jail",
+            LocationEnum::StCharlesPlace => "St. Charles Place",
+            LocationEnum::ElectricCompany => "Electric Company",
+            LocationEnum::StateAvenue => "States Avenue",
+            LocationEnum::VirginiaAvenue => "Virginia Avenue",
+            LocationEnum::PennsylvaniaRailroad => "Pennsylvania Railroad",
+            LocationEnum::StJamesPlace => "St. James Place",
+            LocationEnum::CommunityChest2 => "Community Chest",
+            LocationEnum::TennesseeAvenue => "Tennessee Avenue",
+            LocationEnum::NewYorkAvenue => "New York Avenue",
+            LocationEnum::FreeParking => "Free Parking",
+            LocationEnum::KentuckyAvenue => "Kentucky Avenue",
+            LocationEnum::Chance2 => "Chance",
+            LocationEnum::IndianaAvenue => "Indiana Avenue",
+            LocationEnum::IllinoisAvenue => "Illinois Avenue",
+            LocationEnum::BnORailroad => "B&O Railroad",
+            LocationEnum::AtlanticAvenue => "Atlantic Avenue",
+            LocationEnum::VentnorAvenue => "Ventnor Avenue",
+            LocationEnum::WaterWorks => "Water Works",
+            LocationEnum::MarvinGardens => "Marvin Gardens",
+            LocationEnum::GoToJail => "Go To Jail",
+            LocationEnum::PacificAvenue => "Pacific Avenue",
+            LocationEnum::NorthCarolinaAvenue => "North Carolina Avenue",
+            LocationEnum::CommunityChest3 => "Community Chest",
+            LocationEnum::PennsylvaniaAvenue => "Pennsylvania Avenue",
+            LocationEnum::ShortLine => "Short Line",
+            LocationEnum::Chance3 => "Chance",
+            LocationEnum::ParkPlace => "Park Place",
+            LocationEnum::LuxuryTax => "Luxury Tax",
+            LocationEnum::Boardwalk => "Boardwalk"
+        }
+    }
+
+    pub fn get_color_group(&self) -> Option<ColorGroupEnum> {
+        ColorGroupEnum::all().into_iter()
+            .find(|group| group.get_locations().contains(self))
+    }
+
+    pub fn is_purchasable(&self) -> bool {
+        matches!(self.get_location_type(),
+            LocationTypeEnum::Property | LocationTypeEnum::Railroad | LocationTypeEnum::Utility)
+    }
+
+    /// The square `spaces` ahead, wrapping past Go. Negative `spaces` move backwards.
+    pub fn advance(&self, spaces: i32) -> LocationEnum {
+        let index = (*self as i32 - 1 + spaces % BOARD_SIZE).rem_euclid(BOARD_SIZE);
+        LocationEnum::try_from(index as usize + 1).expect("A wrapped index is always on the board!")
+    }
 
+    pub fn retreat(&self, spaces: i32) -> LocationEnum {
+        self.advance(-(spaces % BOARD_SIZE))
+    }
+
+    /// How many spaces forward `other` is from here, from 0 to 39.
+    pub fn distance_to(&self, other: LocationEnum) -> i32 {
+        (other as i32 - *self as i32).rem_euclid(BOARD_SIZE)
+    }
 }
 
-impl From<usize> for LocationEnum {
-    fn from(value: usize) -> Self {
-        match value {
+impl TryFrom<usize> for LocationEnum {
+    type Error = GameError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        Ok(match value {
             1 => LocationEnum::Go,
             2 => LocationEnum::MediterraneanAvenue,
             3 => LocationEnum::CommunityChest1,
@@ -151,39 +267,74 @@ impl From<usize> for LocationEnum {
             38 => LocationEnum::ParkPlace,
             39 => LocationEnum::LuxuryTax,
             40 => LocationEnum::Boardwalk,
-            _ => panic!("No location found for this number!")
-        }
+            _ => return Err(GameError::InvalidLocation(value))
+        })
     }
 }
 
 pub struct Property {
-    location: LocationEnum,
-    location_type: LocationTypeEnum,
-    cost: i32,
-    owned_by_player_number: Option<usize>,
-    houses: i32,
-    hotels: i32,
-    custom_fn: fn(&mut Player) -> bool,
+    pub location: LocationEnum,
+    pub location_type: LocationTypeEnum,
+    pub cost: i32,
+    pub mortgage_value: i32,
+    pub house_cost: i32,
+    /// Streets list rent unimproved then with 1-4 houses and a hotel, railroads list rent
+    /// by the number of railroads owned and utilities list the dice multiplier.
+    pub rent: Vec<i32>,
+    pub owned_by_player_number: Option<usize>,
+    pub houses: i32,
+    pub hotels: i32,
+    pub is_mortgaged: bool,
+    action: Rc<dyn SquareAction>,
 }
 
 impl Property {
     pub fn new(location: LocationEnum,
                location_type: LocationTypeEnum,
                cost: i32,
-               custom_fn: fn(&mut Player) -> bool) -> Self {
+               action: Rc<dyn SquareAction>) -> Self {
         Self {
             location,
             location_type,
             cost,
+            mortgage_value: cost / 2,
+            house_cost: 0,
+            rent: Vec::new(),
             owned_by_player_number: None,
             houses: 0,
             hotels: 0,
-            custom_fn
+            is_mortgaged: false,
+            action
         }
     }
 
-    pub fn run_fn(&self, player: &mut Player) -> bool {
-        (&self.custom_fn)(player)
+    pub fn new_deed(location: LocationEnum, cost: i32, house_cost: i32, rent: &[i32]) -> Self {
+        let mut property = Property::new(location, location.get_location_type(), cost, Rc::new(deed_action));
+        property.house_cost = house_cost;
+        property.rent = rent.to_vec();
+        property
+    }
+
+    pub fn get_action(&self) -> Rc<dyn SquareAction> {
+        self.action.clone()
+    }
+
+    pub fn is_purchasable(&self) -> bool {
+        self.location.is_purchasable()
+    }
+
+    pub fn get_color_group(&self) -> Option<ColorGroupEnum> {
+        self.location.get_color_group()
+    }
+
+    /// The 10% interest due when lifting a mortgage, rounded up.
+    pub fn mortgage_interest(&self) -> i32 {
+        (self.mortgage_value + 9) / 10
+    }
+
+    /// Houses on the square, counting a hotel as a fifth house.
+    pub fn building_level(&self) -> i32 {
+        self.houses + self.hotels * 5
     }
 }
 
@@ -203,51 +354,180 @@ impl Properties {
         }
     }
 
+    /// Replaces what happens when a player lands on `location`.
+    pub fn set_action(&mut self, location: LocationEnum, action: Rc<dyn SquareAction>) {
+        self.get_property(location).action = action;
+    }
+
+    pub fn get_property_ref(&self, location: LocationEnum) -> &Property {
+        match self.list.get(&(location as usize)) {
+            Some(property) => property,
+            None => panic!("Could not find property!")
+        }
+    }
+
+    pub fn count_owned_of_type(&self, player_number: usize, location_type: LocationTypeEnum) -> i32 {
+        self.list.values()
+            .filter(|p| p.location_type == location_type && p.owned_by_player_number == Some(player_number))
+            .count() as i32
+    }
+
+    /// Total (houses, hotels) standing on `player_number`'s streets.
+    pub fn count_buildings(&self, player_number: usize) -> (i32, i32) {
+        self.list.values()
+            .filter(|p| p.owned_by_player_number == Some(player_number))
+            .fold((0, 0), |(houses, hotels), p| (houses + p.houses, hotels + p.hotels))
+    }
+
+    /// Whether `player_number` owns every street in `color_group`.
+    pub fn has_monopoly(&self, player_number: usize, color_group: ColorGroupEnum) -> bool {
+        color_group.get_locations().into_iter()
+            .all(|location| self.get_property_ref(location).owned_by_player_number == Some(player_number))
+    }
+
+    pub fn get_monopolies(&self, player_number: usize) -> Vec<ColorGroupEnum> {
+        ColorGroupEnum::all().into_iter()
+            .filter(|group| self.has_monopoly(player_number, *group))
+            .collect()
+    }
+
+    /// Rent owed for landing on `location`, or 0 when nobody owns it.
+    /// `dice_total` is only used for utilities.
+    pub fn get_rent(&self, location: LocationEnum, dice_total: i32) -> i32 {
+        let property = self.get_property_ref(location);
+        let owner = match property.owned_by_player_number {
+            Some(owner) if !property.is_mortgaged => owner,
+            _ => return 0,
+        };
+
+        match property.location_type {
+            LocationTypeEnum::Property => {
+                let level = property.building_level();
+                match property.get_color_group() {
+                    Some(group) if level == 0 && self.has_monopoly(owner, group) => property.rent[0] * 2,
+                    _ => property.rent[level as usize],
+                }
+            },
+            LocationTypeEnum::Railroad | LocationTypeEnum::Utility => {
+                let owned = self.count_owned_of_type(owner, property.location_type) as usize;
+                let rent = property.rent[owned - 1];
+                if property.location_type == LocationTypeEnum::Utility { rent * dice_total } else { rent }
+            },
+            _ => 0,
+        }
+    }
+
     pub fn get_new_list() -> Self {
         let mut init = Self {
             list: HashMap::new()
         };
-        init.add(Property::new(LocationEnum::Go, LocationTypeEnum::Go, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::MediterraneanAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::CommunityChest1, LocationTypeEnum::CommunityChest, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::BalticAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::IncomeTax, LocationTypeEnum::Tax, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::ReadingRailroad, LocationTypeEnum::Railroad, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::OrientalAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::Chance1, LocationTypeEnum::Chance, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::VermontAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::ConnecticutAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::Jail, LocationTypeEnum::Jail, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::StCharlesPlace, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::ElectricCompany, LocationTypeEnum::Utility, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::StateAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::VirginiaAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::PennsylvaniaRailroad, LocationTypeEnum::Railroad, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::StJamesPlace, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::CommunityChest2, LocationTypeEnum::CommunityChest, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::TennesseeAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::NewYorkAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::FreeParking, LocationTypeEnum::None, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::KentuckyAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::Chance2, LocationTypeEnum::Chance, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::IndianaAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::IllinoisAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::BnORailroad, LocationTypeEnum::Railroad, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::AtlanticAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::VentnorAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::WaterWorks, LocationTypeEnum::Utility, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::MarvinGardens, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::GoToJail, LocationTypeEnum::None, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::PacificAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::NorthCarolinaAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::CommunityChest3, LocationTypeEnum::CommunityChest, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::PennsylvaniaAvenue, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::ShortLine, LocationTypeEnum::Railroad, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::Chance3, LocationTypeEnum::Chance, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::ParkPlace, LocationTypeEnum::Property, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::LuxuryTax, LocationTypeEnum::Tax, 0, |player| { true }));
-        init.add(Property::new(LocationEnum::Boardwalk, LocationTypeEnum::Property, 0, |player| { true }));
+        init.add(Property::new(LocationEnum::Go, LocationTypeEnum::Go, 0, Rc::new(no_action)));
+        init.add(Property::new_deed(LocationEnum::MediterraneanAvenue, 60, 50, &[2, 10, 30, 90, 160, 250]));
+        init.add(Property::new(LocationEnum::CommunityChest1, LocationTypeEnum::CommunityChest, 0, Rc::new(community_chest_action)));
+        init.add(Property::new_deed(LocationEnum::BalticAvenue, 60, 50, &[4, 20, 60, 180, 320, 450]));
+        init.add(Property::new(LocationEnum::IncomeTax, LocationTypeEnum::Tax, 0, tax_action(200)));
+        init.add(Property::new_deed(LocationEnum::ReadingRailroad, 200, 0, &[25, 50, 100, 200]));
+        init.add(Property::new_deed(LocationEnum::OrientalAvenue, 100, 50, &[6, 30, 90, 270, 400, 550]));
+        init.add(Property::new(LocationEnum::Chance1, LocationTypeEnum::Chance, 0, Rc::new(chance_action)));
+        init.add(Property::new_deed(LocationEnum::VermontAvenue, 100, 50, &[6, 30, 90, 270, 400, 550]));
+        init.add(Property::new_deed(LocationEnum::ConnecticutAvenue, 120, 50, &[8, 40, 100, 300, 450, 600]));
+        init.add(Property::new(LocationEnum::Jail, LocationTypeEnum::Jail, 0, Rc::new(no_action)));
+        init.add(Property::new_deed(LocationEnum::StCharlesPlace, 140, 100, &[10, 50, 150, 450, 625, 750]));
+        init.add(Property::new_deed(LocationEnum::ElectricCompany, 150, 0, &[4, 10]));
+        init.add(Property::new_deed(LocationEnum::StateAvenue, 140, 100, &[10, 50, 150, 450, 625, 750]));
+        init.add(Property::new_deed(LocationEnum::VirginiaAvenue, 160, 100, &[12, 60, 180, 500, 700, 900]));
+        init.add(Property::new_deed(LocationEnum::PennsylvaniaRailroad, 200, 0, &[25, 50, 100, 200]));
+        init.add(Property::new_deed(LocationEnum::StJamesPlace, 180, 100, &[14, 70, 200, 550, 750, 950]));
+        init.add(Property::new(LocationEnum::CommunityChest2, LocationTypeEnum::CommunityChest, 0, Rc::new(community_chest_action)));
+        init.add(Property::new_deed(LocationEnum::TennesseeAvenue, 180, 100, &[14, 70, 200, 550, 750, 950]));
+        init.add(Property::new_deed(LocationEnum::NewYorkAvenue, 200, 100, &[16, 80, 220, 600, 800, 1000]));
+        init.add(Property::new(LocationEnum::FreeParking, LocationTypeEnum::None, 0, Rc::new(free_parking_action)));
+        init.add(Property::new_deed(LocationEnum::KentuckyAvenue, 220, 150, &[18, 90, 250, 700, 875, 1050]));
+        init.add(Property::new(LocationEnum::Chance2, LocationTypeEnum::Chance, 0, Rc::new(chance_action)));
+        init.add(Property::new_deed(LocationEnum::IndianaAvenue, 220, 150, &[18, 90, 250, 700, 875, 1050]));
+        init.add(Property::new_deed(LocationEnum::IllinoisAvenue, 240, 150, &[20, 100, 300, 750, 925, 1100]));
+        init.add(Property::new_deed(LocationEnum::BnORailroad, 200, 0, &[25, 50, 100, 200]));
+        init.add(Property::new_deed(LocationEnum::AtlanticAvenue, 260, 150, &[22, 110, 330, 800, 975, 1150]));
+        init.add(Property::new_deed(LocationEnum::VentnorAvenue, 260, 150, &[22, 110, 330, 800, 975, 1150]));
+        init.add(Property::new_deed(LocationEnum::WaterWorks, 150, 0, &[4, 10]));
+        init.add(Property::new_deed(LocationEnum::MarvinGardens, 280, 150, &[24, 120, 360, 850, 1025, 1200]));
+        init.add(Property::new(LocationEnum::GoToJail, LocationTypeEnum::None, 0, Rc::new(go_to_jail_action)));
+        init.add(Property::new_deed(LocationEnum::PacificAvenue, 300, 200, &[26, 130, 390, 900, 1100, 1275]));
+        init.add(Property::new_deed(LocationEnum::NorthCarolinaAvenue, 300, 200, &[26, 130, 390, 900, 1100, 1275]));
+        init.add(Property::new(LocationEnum::CommunityChest3, LocationTypeEnum::CommunityChest, 0, Rc::new(community_chest_action)));
+        init.add(Property::new_deed(LocationEnum::PennsylvaniaAvenue, 320, 200, &[28, 150, 450, 1000, 1200, 1400]));
+        init.add(Property::new_deed(LocationEnum::ShortLine, 200, 0, &[25, 50, 100, 200]));
+        init.add(Property::new(LocationEnum::Chance3, LocationTypeEnum::Chance, 0, Rc::new(chance_action)));
+        init.add(Property::new_deed(LocationEnum::ParkPlace, 350, 200, &[35, 175, 500, 1100, 1300, 1500]));
+        init.add(Property::new(LocationEnum::LuxuryTax, LocationTypeEnum::Tax, 0, tax_action(100)));
+        init.add(Property::new_deed(LocationEnum::Boardwalk, 400, 200, &[50, 200, 600, 1400, 1700, 2000]));
 
         init
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::GameError;
+    use crate::location::{ColorGroupEnum, LocationEnum, LocationTypeEnum, Properties};
+
+    #[test]
+    fn movement_wraps_around_the_board() {
+        assert_eq!(LocationEnum::ParkPlace.advance(2), LocationEnum::Boardwalk);
+        assert_eq!(LocationEnum::Boardwalk.advance(1), LocationEnum::Go);
+        assert_eq!(LocationEnum::LuxuryTax.advance(13), LocationEnum::StCharlesPlace);
+        assert_eq!(LocationEnum::Go.advance(80), LocationEnum::Go);
+        assert_eq!(LocationEnum::Chance1.retreat(3), LocationEnum::IncomeTax);
+        assert_eq!(LocationEnum::CommunityChest1.retreat(3), LocationEnum::Boardwalk);
+        assert_eq!(LocationEnum::Go.advance(i32::MAX), LocationEnum::Chance1);
+        assert_eq!(LocationEnum::Go.advance(i32::MIN), LocationEnum::NorthCarolinaAvenue);
+        assert_eq!(LocationEnum::Go.retreat(i32::MIN), LocationEnum::VermontAvenue);
+    }
+
+    #[test]
+    fn distance_is_measured_forwards() {
+        assert_eq!(LocationEnum::Go.distance_to(LocationEnum::Boardwalk), 39);
+        assert_eq!(LocationEnum::Boardwalk.distance_to(LocationEnum::Go), 1);
+        assert_eq!(LocationEnum::Jail.distance_to(LocationEnum::Jail), 0);
+    }
+
+    #[test]
+    fn only_board_squares_convert_from_numbers() {
+        assert_eq!(LocationEnum::try_from(40), Ok(LocationEnum::Boardwalk));
+        assert_eq!(LocationEnum::try_from(0), Err(GameError::InvalidLocation(0)));
+        assert_eq!(LocationEnum::try_from(41), Err(GameError::InvalidLocation(41)));
+    }
+
+    #[test]
+    fn every_street_belongs_to_a_color_group() {
+        let streets = (1..=40).map(|number| LocationEnum::try_from(number).unwrap())
+            .filter(|location| location.get_location_type() == LocationTypeEnum::Property);
+        for street in streets {
+            assert!(street.get_color_group().is_some(), "{:?} has no color group", street);
+        }
+        assert_eq!(LocationEnum::ReadingRailroad.get_color_group(), None);
+        assert_eq!(LocationEnum::Boardwalk.get_color_group(), Some(ColorGroupEnum::DarkBlue));
+    }
+
+    #[test]
+    fn owning_every_street_in_a_group_is_a_monopoly() {
+        let mut properties = Properties::get_new_list();
+        properties.get_property(LocationEnum::ParkPlace).owned_by_player_number = Some(1);
+        assert!(!properties.has_monopoly(1, ColorGroupEnum::DarkBlue));
+        properties.get_property(LocationEnum::Boardwalk).owned_by_player_number = Some(2);
+        assert!(!properties.has_monopoly(1, ColorGroupEnum::DarkBlue));
+        properties.get_property(LocationEnum::Boardwalk).owned_by_player_number = Some(1);
+        assert!(properties.has_monopoly(1, ColorGroupEnum::DarkBlue));
+        assert_eq!(properties.get_monopolies(1), vec![ColorGroupEnum::DarkBlue]);
+    }
+
+    #[test]
+    fn unimproved_rent_doubles_with_a_monopoly() {
+        let mut properties = Properties::get_new_list();
+        properties.get_property(LocationEnum::MediterraneanAvenue).owned_by_player_number = Some(1);
+        assert_eq!(properties.get_rent(LocationEnum::MediterraneanAvenue, 7), 2);
+        properties.get_property(LocationEnum::BalticAvenue).owned_by_player_number = Some(1);
+        assert_eq!(properties.get_rent(LocationEnum::MediterraneanAvenue, 7), 4);
+        assert_eq!(properties.get_rent(LocationEnum::BalticAvenue, 7), 8);
+    }
+}
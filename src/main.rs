@@ -1,58 +1,37 @@
 use std::cell::RefCell;
+use std::env;
+use std::path::Path;
+use std::process;
 use std::rc::Rc;
-use crate::board_state::BoardState;
-use crate::dice::{Dice};
-use crate::player::Player;
-
-mod board_state;
-mod player;
-mod location;
-mod cards;
-mod dice;
+use monopoly_rs::rules::GameRules;
+use monopoly_rs::simulation::{parse_args, run_simulation};
+use monopoly_rs::terminal::{run_game, Terminal};
 
 fn main() {
-
-}
-
-fn create_new_game(player_count: i32) -> BoardState {
-    let roll_engine = Rc::new(RefCell::new(Dice{}));
-    let mut board = BoardState::new(roll_engine);
-    board.add_players(create_new_players(player_count));
-    board
-}
-
-fn create_new_players(player_count: i32) -> Vec<Player> {
-    Player::create_players(player_count)
-}
-
-fn create_new_player(player_number: i32) -> Player {
-    Player::new(player_number as usize)
-}
-
-#[cfg(test)]
-mod test {
-    use crate::{create_new_game, create_new_player, create_new_players};
-
-    #[test]
-    fn can_create_new_game() {
-        let board = create_new_game(5);
-        assert_eq!(5, board.players.len());
-        assert_eq!(1, board.player_turn);
-    }
-
-    #[test]
-    fn can_add_players_to_board() {
-        let mut board = create_new_game(0);
-        assert_eq!(0, board.players.len());
-        board.add_players(create_new_players(2));
-        assert_eq!(2, board.players.len());
-    }
-
-    #[test]
-    fn can_add_player_to_board() {
-        let mut board = create_new_game(0);
-        assert_eq!(0, board.players.len());
-        board.add_player(create_new_player(1));
-        assert_eq!(1, board.players.len());
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("simulate") => match parse_args(&args[1..]) {
+            Ok(config) => print!("{}", run_simulation(&config)),
+            Err(error) => {
+                eprintln!("simulate: {}", error);
+                process::exit(2);
+            },
+        },
+        Some("--rules") => match args.get(1).map(|path| GameRules::load(Path::new(path))) {
+            Some(Ok(rules)) => {
+                run_game(Rc::new(RefCell::new(Terminal::stdio())), rules);
+            },
+            Some(Err(error)) => {
+                eprintln!("{}", error);
+                process::exit(2);
+            },
+            None => {
+                eprintln!("--rules needs a file");
+                process::exit(2);
+            },
+        },
+        _ => {
+            run_game(Rc::new(RefCell::new(Terminal::stdio())), GameRules::default());
+        },
     }
-}
\ No newline at end of file
+}
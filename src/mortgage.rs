@@ -0,0 +1,148 @@
+use crate::board_state::BoardState;
+use crate::error::GameError;
+use crate::events::GameEvent;
+use crate::location::LocationEnum;
+
+impl BoardState {
+    /// Mortgages `location`, paying its owner the mortgage value.
+    pub fn mortgage_property(&mut self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        self.check_owns_deed(player_number, location)?;
+        let property = self.properties.get_property_ref(location);
+        if property.is_mortgaged {
+            return Err(GameError::Mortgaged(location));
+        }
+        if let Some(group) = property.get_color_group() {
+            if self.highest_building_level(group) > 0 {
+                return Err(GameError::GroupHasBuildings(group));
+            }
+        }
+
+        let mortgage_value = property.mortgage_value;
+        self.properties.get_property(location).is_mortgaged = true;
+        self.get_current_player_by_number(player_number).cash += mortgage_value;
+        self.emit(GameEvent::Mortgaged { player_number, location });
+        Ok(())
+    }
+
+    /// Lifts the mortgage on `location` for its mortgage value plus 10% interest.
+    pub fn unmortgage_property(&mut self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        self.check_owns_deed(player_number, location)?;
+        let property = self.properties.get_property_ref(location);
+        if !property.is_mortgaged {
+            return Err(GameError::NotMortgaged(location));
+        }
+
+        let cost = property.mortgage_value + property.mortgage_interest();
+        let player = self.get_current_player_by_number(player_number);
+        if player.cash < cost {
+            return Err(GameError::InsufficientFunds { needed: cost, available: player.cash });
+        }
+        player.cash -= cost;
+        self.properties.get_property(location).is_mortgaged = false;
+        self.emit(GameEvent::Unmortgaged { player_number, location });
+        Ok(())
+    }
+
+    /// Hands `location` to `to_player_number`. A mortgaged deed costs the new owner 10% interest
+    /// straight away, and when `lift_mortgage` is set they also repay the mortgage value now.
+    pub fn transfer_property(&mut self, location: LocationEnum, to_player_number: usize, lift_mortgage: bool) -> Result<(), GameError> {
+        let property = self.properties.get_property_ref(location);
+        let cost = match (property.is_mortgaged, lift_mortgage) {
+            (true, true) => property.mortgage_value + property.mortgage_interest(),
+            (true, false) => property.mortgage_interest(),
+            (false, _) => 0,
+        };
+
+        let player = self.get_current_player_by_number(to_player_number);
+        if player.cash < cost {
+            return Err(GameError::InsufficientFunds { needed: cost, available: player.cash });
+        }
+        player.cash -= cost;
+        let property = self.properties.get_property(location);
+        property.owned_by_player_number = Some(to_player_number);
+        if lift_mortgage {
+            property.is_mortgaged = false;
+        }
+        Ok(())
+    }
+
+    fn check_owns_deed(&self, player_number: usize, location: LocationEnum) -> Result<(), GameError> {
+        let property = self.properties.get_property_ref(location);
+        if !property.is_purchasable() {
+            return Err(GameError::PropertyNotPurchasable(location));
+        }
+        if property.owned_by_player_number != Some(player_number) {
+            return Err(GameError::NotOwner(location));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dice::DiceRoll;
+    use crate::error::GameError;
+    use crate::location::{ColorGroupEnum, LocationEnum};
+    use crate::test_support::get_new_board;
+
+    #[test]
+    fn mortgaging_pays_the_mortgage_value() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::Boardwalk).unwrap();
+        board.mortgage_property(1, LocationEnum::Boardwalk).unwrap();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1300);
+        assert!(board.properties.get_property(LocationEnum::Boardwalk).is_mortgaged);
+        assert_eq!(board.mortgage_property(1, LocationEnum::Boardwalk), Err(GameError::Mortgaged(LocationEnum::Boardwalk)));
+    }
+
+    #[test]
+    fn unmortgaging_costs_the_mortgage_value_plus_ten_percent() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::MediterraneanAvenue).unwrap();
+        board.mortgage_property(1, LocationEnum::MediterraneanAvenue).unwrap();
+        board.unmortgage_property(1, LocationEnum::MediterraneanAvenue).unwrap();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1500 - 60 + 30 - 33);
+        assert!(!board.properties.get_property(LocationEnum::MediterraneanAvenue).is_mortgaged);
+    }
+
+    #[test]
+    fn cannot_mortgage_someone_elses_property() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(2, LocationEnum::Boardwalk).unwrap();
+        let result = board.mortgage_property(1, LocationEnum::Boardwalk);
+        assert_eq!(result, Err(GameError::NotOwner(LocationEnum::Boardwalk)));
+    }
+
+    #[test]
+    fn mortgaged_property_collects_no_rent() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::ReadingRailroad).unwrap();
+        board.mortgage_property(1, LocationEnum::ReadingRailroad).unwrap();
+        assert_eq!(board.properties.get_rent(LocationEnum::ReadingRailroad, 7), 0);
+    }
+
+    #[test]
+    fn cannot_mortgage_while_the_color_group_has_buildings() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::ParkPlace).unwrap();
+        board.buy_property(1, LocationEnum::Boardwalk).unwrap();
+        board.buy_house(1, LocationEnum::Boardwalk).unwrap();
+        let result = board.mortgage_property(1, LocationEnum::ParkPlace);
+        assert_eq!(result, Err(GameError::GroupHasBuildings(ColorGroupEnum::DarkBlue)));
+    }
+
+    #[test]
+    fn receiving_a_mortgaged_property_costs_interest() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::Boardwalk).unwrap();
+        board.mortgage_property(1, LocationEnum::Boardwalk).unwrap();
+        board.transfer_property(LocationEnum::Boardwalk, 2, false).unwrap();
+        assert_eq!(board.get_current_player_by_number(2).cash, 1480);
+        assert!(board.properties.get_property(LocationEnum::Boardwalk).is_mortgaged);
+
+        board.transfer_property(LocationEnum::Boardwalk, 1, true).unwrap();
+        assert_eq!(board.get_current_player_by_number(1).cash, 1300 - 220);
+        assert!(!board.properties.get_property(LocationEnum::Boardwalk).is_mortgaged);
+    }
+}
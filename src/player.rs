@@ -1,55 +1,100 @@
+use serde::{Deserialize, Serialize};
+use crate::cards::DeckEnum;
 use crate::dice::DiceRoll;
 use crate::location::LocationEnum;
+use crate::rules::GameRules;
 
+pub const STARTING_CASH: i32 = 1500;
+pub const GO_SALARY: i32 = 200;
+pub const JAIL_FINE: i32 = 50;
+pub const JAIL_ROLL_ATTEMPTS: i32 = 3;
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub player_number: usize,
+    pub name: String,
     pub cash: i32,
     pub current_location: LocationEnum,
     pub is_in_jail: bool,
     pub doubles_roll_count: i32,
     pub doubles_roll_jail_count: i32,
+    pub get_out_of_jail_free_cards: Vec<DeckEnum>,
+    pub is_bankrupt: bool,
 }
 
 impl Player {
-    pub fn new(player_number: usize) -> Self {
+    /// A new player on Go with the starting cash `rules` give.
+    pub fn new(player_number: usize, rules: &GameRules) -> Self {
         Player {
             player_number,
-            cash: 1500,
+            name: format!("Player {}", player_number),
+            cash: rules.starting_cash,
             current_location: LocationEnum::Go,
             is_in_jail: false,
             doubles_roll_count: 0,
-            doubles_roll_jail_count: 0
+            doubles_roll_jail_count: 0,
+            get_out_of_jail_free_cards: Vec::new(),
+            is_bankrupt: false,
         }
     }
 
-    pub fn create_players(player_count: i32) -> Vec<Player> {
+    pub fn create_players(player_count: i32, rules: &GameRules) -> Vec<Player> {
         let mut players: Vec<Player> = Vec::new();
         for i in 1..player_count + 1 {
-            players.push(Player::new(i as usize));
+            players.push(Player::new(i as usize, rules));
         }
 
         players
     }
 
-    pub fn update_location(&mut self, roll: &DiceRoll) {
+    /// Counts consecutive doubles, returning true when this roll is the third in a row.
+    pub fn record_doubles(&mut self, roll: &DiceRoll) -> bool {
+        if self.is_in_jail || !roll.is_doubles() {
+            self.doubles_roll_count = 0;
+            return false;
+        }
+
+        self.doubles_roll_count += 1;
+        self.doubles_roll_count >= 3
+    }
+
+    pub fn update_location(&mut self, roll: &DiceRoll, rules: &GameRules) {
         if self.is_in_jail {
-            if self.doubles_roll_jail_count < 3 && !roll.is_doubles() {
+            if roll.is_doubles() {
+                self.remove_from_jail();
+            } else if self.doubles_roll_jail_count + 1 < rules.jail_roll_attempts {
                 self.doubles_roll_jail_count += 1;
                 return
             } else {
+                // The last failed attempt forces the fine, then the player moves by this roll.
+                self.cash -= rules.jail_fine;
                 self.remove_from_jail();
             }
         }
 
+        self.move_forward(roll.total(), rules);
+    }
+
+    /// Moves `spaces` ahead, collecting salary if Go is passed.
+    pub fn move_forward(&mut self, spaces: i32, rules: &GameRules) {
+        let last_location = self.current_location;
+        self.current_location = last_location.advance(spaces);
+
+        self.check_if_passed_go(last_location, self.current_location, rules);
+    }
+
+    /// Moves forward to `location` without rolling, collecting salary if Go is passed.
+    pub fn advance_to(&mut self, location: LocationEnum, rules: &GameRules) {
         let last_location = self.current_location;
-        self.current_location = LocationEnum::from(((last_location as i32 + roll.0 + roll.1) % 40) as usize);
+        self.current_location = location;
 
-        self.check_if_passed_go(last_location, self.current_location);
+        self.check_if_passed_go(last_location, self.current_location, rules);
     }
 
     pub fn send_to_jail(&mut self) {
         self.current_location = LocationEnum::Jail;
         self.is_in_jail = true;
+        self.doubles_roll_count = 0;
     }
 
     pub fn remove_from_jail(&mut self) {
@@ -57,9 +102,12 @@ impl Player {
         self.doubles_roll_jail_count = 0;
     }
 
-    pub fn check_if_passed_go(&mut self, old_location: LocationEnum, new_location: LocationEnum) {
+    pub fn check_if_passed_go(&mut self, old_location: LocationEnum, new_location: LocationEnum, rules: &GameRules) {
         if new_location < old_location {
-            self.cash += 200;
+            self.cash += rules.go_salary;
+            if new_location == LocationEnum::Go && rules.double_salary_on_go {
+                self.cash += rules.go_salary;
+            }
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,289 @@
+use std::fmt;
+use crate::board_state::BoardState;
+use crate::location::{LocationEnum, LocationTypeEnum};
+
+const CELL_WIDTH: usize = 8;
+const CELL_HEIGHT: usize = 3;
+const SIDE: usize = 11;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum BorderStyleEnum {
+    Ascii,
+    /// Box-drawing characters, for terminals that support them.
+    Unicode,
+}
+
+/// Draws the board as a ring of squares with Go in the bottom right corner. Each square shows
+/// its name, then the owner (`P2`) with a house per `^`, `H` for a hotel or `M` when mortgaged
+/// (or the price while unowned), then the numbers of the players standing on it. Players in
+/// jail are bracketed. A summary of every player fills the middle.
+pub fn render_board(board: &BoardState, style: BorderStyleEnum) -> String {
+    let width = SIDE * (CELL_WIDTH + 1) + 1;
+    let height = SIDE * (CELL_HEIGHT + 1) + 1;
+    let mut canvas = vec![vec![' '; width]; height];
+
+    for number in 1..=40 {
+        let location = LocationEnum::try_from(number).expect("Every square from 1 to 40 exists!");
+        let (row, column) = get_cell(location);
+        draw_box(&mut canvas, row * (CELL_HEIGHT + 1), column * (CELL_WIDTH + 1));
+        for (line, text) in describe_square(board, location).iter().enumerate() {
+            write_text(&mut canvas, row * (CELL_HEIGHT + 1) + 1 + line, column * (CELL_WIDTH + 1) + 1, text, CELL_WIDTH);
+        }
+    }
+
+    let inner_width = (SIDE - 2) * (CELL_WIDTH + 1) - 3;
+    for (line, text) in describe_players(board).iter().enumerate() {
+        write_text(&mut canvas, CELL_HEIGHT + 2 + line, CELL_WIDTH + 3, text, inner_width);
+    }
+
+    if style == BorderStyleEnum::Unicode {
+        canvas = to_unicode(&canvas);
+    }
+    canvas.iter()
+        .map(|line| line.iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+impl fmt::Display for BoardState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", render_board(self, BorderStyleEnum::Ascii))
+    }
+}
+
+// Failed assertions print the whole board rather than a wall of hash maps.
+impl fmt::Debug for BoardState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\n{}", render_board(self, BorderStyleEnum::Ascii))
+    }
+}
+
+/// Row and column of a square, counting from the top left corner.
+fn get_cell(location: LocationEnum) -> (usize, usize) {
+    let index = location as usize - 1;
+    match index {
+        0..=10 => (SIDE - 1, SIDE - 1 - index),
+        11..=19 => (SIDE - 1 - (index - 10), 0),
+        20..=30 => (0, index - 20),
+        _ => (index - 30, SIDE - 1),
+    }
+}
+
+fn describe_square(board: &BoardState, location: LocationEnum) -> [String; 3] {
+    let property = board.properties.get_property_ref(location);
+    let holding = match property.owned_by_player_number {
+        Some(owner) if property.is_mortgaged => format!("P{} M", owner),
+        Some(owner) if property.hotels > 0 => format!("P{} H", owner),
+        Some(owner) => format!("P{} {}", owner, "^".repeat(property.houses as usize)).trim_end().to_string(),
+        None if property.is_purchasable() => format!("${}", property.cost),
+        None => String::new(),
+    };
+
+    let mut tokens: Vec<String> = Vec::new();
+    for player_number in 1..=board.players.len() {
+        let player = &board.players[&player_number];
+        if player.is_bankrupt || player.current_location != location {
+            continue;
+        }
+        tokens.push(if player.is_in_jail { format!("[{}]", player_number) } else { player_number.to_string() });
+    }
+
+    [get_short_name(location).to_string(), holding, tokens.join("")]
+}
+
+fn describe_players(board: &BoardState) -> Vec<String> {
+    let mut lines = Vec::new();
+    for player_number in 1..=board.players.len() {
+        let player = &board.players[&player_number];
+        let marker = if player_number == board.player_turn { ">" } else { " " };
+        let status = if player.is_bankrupt {
+            "bankrupt".to_string()
+        } else {
+            let jail = if player.is_in_jail { ", in jail" } else { "" };
+            format!("${} on {}{}", player.cash, player.current_location.get_name(), jail)
+        };
+        lines.push(format!("{}{} {}: {}", marker, player_number, player.name, status));
+    }
+    lines.push(String::new());
+    lines.push(format!("Bank: {} houses, {} hotels", board.houses_available, board.hotels_available));
+    if board.rules.free_parking_jackpot {
+        lines.push(format!("Free Parking pot: ${}", board.free_parking_pot));
+    }
+    lines
+}
+
+fn get_short_name(location: LocationEnum) -> &'static str {
+    match location.get_location_type() {
+        LocationTypeEnum::Chance => return "Chance",
+        LocationTypeEnum::CommunityChest => return "Chest",
+        _ => {}
+    }
+    match location {
+        LocationEnum::Go => "GO",
+        LocationEnum::MediterraneanAvenue => "Medit.",
+        LocationEnum::BalticAvenue => "Baltic",
+        LocationEnum::IncomeTax => "Inc Tax",
+        LocationEnum::ReadingRailroad => "Reading",
+        LocationEnum::OrientalAvenue => "Oriental",
+        LocationEnum::VermontAvenue => "Vermont",
+        LocationEnum::ConnecticutAvenue => "Conn.",
+        LocationEnum::Jail => "Jail",
+        LocationEnum::StCharlesPlace => "St.Chas",
+        LocationEnum::ElectricCompany => "Electric",
+        LocationEnum::StateAvenue => "States",
+        LocationEnum::VirginiaAvenue => "Virginia",
+        LocationEnum::PennsylvaniaRailroad => "Penn RR",
+        LocationEnum::StJamesPlace => "St.James",
+        LocationEnum::TennesseeAvenue => "Tenn.",
+        LocationEnum::NewYorkAvenue => "New York",
+        LocationEnum::FreeParking => "Parking",
+        LocationEnum::KentuckyAvenue => "Kentucky",
+        LocationEnum::IndianaAvenue => "Indiana",
+        LocationEnum::IllinoisAvenue => "Illinois",
+        LocationEnum::BnORailroad => "B&O RR",
+        LocationEnum::AtlanticAvenue => "Atlantic",
+        LocationEnum::VentnorAvenue => "Ventnor",
+        LocationEnum::WaterWorks => "WaterWks",
+        LocationEnum::MarvinGardens => "Marvin",
+        LocationEnum::GoToJail => "To Jail",
+        LocationEnum::PacificAvenue => "Pacific",
+        LocationEnum::NorthCarolinaAvenue => "N.Carol.",
+        LocationEnum::PennsylvaniaAvenue => "Penn Ave",
+        LocationEnum::ShortLine => "ShortLn",
+        LocationEnum::ParkPlace => "Park Pl",
+        LocationEnum::LuxuryTax => "Lux Tax",
+        LocationEnum::Boardwalk => "Boardwlk",
+        _ => location.get_name(),
+    }
+}
+
+fn draw_box(canvas: &mut [Vec<char>], top: usize, left: usize) {
+    let (bottom, right) = (top + CELL_HEIGHT + 1, left + CELL_WIDTH + 1);
+    for row in [top, bottom] {
+        canvas[row][left..=right].fill('-');
+    }
+    for line in canvas.iter_mut().take(bottom + 1).skip(top) {
+        line[left] = '|';
+        line[right] = '|';
+    }
+    for (y, x) in [(top, left), (top, right), (bottom, left), (bottom, right)] {
+        canvas[y][x] = '+';
+    }
+}
+
+fn write_text(canvas: &mut [Vec<char>], y: usize, x: usize, text: &str, limit: usize) {
+    for (offset, character) in text.chars().take(limit).enumerate() {
+        canvas[y][x + offset] = character;
+    }
+}
+
+// Picks the box-drawing junction for each corner from the lines that meet there.
+fn to_unicode(canvas: &[Vec<char>]) -> Vec<Vec<char>> {
+    let at = |y: isize, x: isize| -> char {
+        if y < 0 || x < 0 {
+            return ' ';
+        }
+        canvas.get(y as usize).and_then(|line| line.get(x as usize)).copied().unwrap_or(' ')
+    };
+    let mut result = canvas.to_vec();
+    for (y, line) in canvas.iter().enumerate() {
+        for (x, character) in line.iter().enumerate() {
+            let (yi, xi) = (y as isize, x as isize);
+            result[y][x] = match character {
+                '-' => '─',
+                '|' => '│',
+                '+' => {
+                    let up = matches!(at(yi - 1, xi), '|' | '+');
+                    let down = matches!(at(yi + 1, xi), '|' | '+');
+                    let left = matches!(at(yi, xi - 1), '-' | '+');
+                    let right = matches!(at(yi, xi + 1), '-' | '+');
+                    match (up, down, left, right) {
+                        (false, true, false, true) => '┌',
+                        (false, true, true, false) => '┐',
+                        (true, false, false, true) => '└',
+                        (true, false, true, false) => '┘',
+                        (false, true, true, true) => '┬',
+                        (true, false, true, true) => '┴',
+                        (true, true, false, true) => '├',
+                        (true, true, true, false) => '┤',
+                        _ => '┼',
+                    }
+                },
+                other => *other,
+            };
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::BoardState;
+    use crate::dice::Dice;
+    use crate::location::{ColorGroupEnum, LocationEnum};
+    use crate::render::{render_board, BorderStyleEnum};
+    use crate::rules::GameRules;
+
+    fn get_new_board() -> BoardState {
+        let mut board = BoardState::new(Rc::new(RefCell::new(Dice{})), GameRules::default());
+        board.seat_new_players(3);
+        board
+    }
+
+    fn get_square_lines(rendered: &str, name: &str) -> Vec<String> {
+        let lines: Vec<&str> = rendered.lines().collect();
+        let border = format!("|{}", name);
+        let (y, line) = lines.iter().enumerate().find(|(_, line)| line.contains(&border)).unwrap();
+        let x = line.find(&border).unwrap() + 1;
+        (0..3).map(|offset| lines[y + offset].chars().skip(x).take(8).collect::<String>().trim_end().to_string()).collect()
+    }
+
+    #[test]
+    fn squares_show_owners_buildings_and_tokens() {
+        let mut board = get_new_board();
+        for location in ColorGroupEnum::DarkBlue.get_locations() {
+            board.buy_property(2, location).unwrap();
+        }
+        board.buy_house(2, LocationEnum::Boardwalk).unwrap();
+        board.buy_house(2, LocationEnum::ParkPlace).unwrap();
+        board.buy_house(2, LocationEnum::Boardwalk).unwrap();
+        board.buy_property(3, LocationEnum::ShortLine).unwrap();
+        board.mortgage_property(3, LocationEnum::ShortLine).unwrap();
+        board.get_current_player_by_number(1).current_location = LocationEnum::Boardwalk;
+        board.get_current_player_by_number(3).send_to_jail();
+
+        let rendered = render_board(&board, BorderStyleEnum::Ascii);
+        assert_eq!(get_square_lines(&rendered, "Boardwlk"), vec!["Boardwlk", "P2 ^^", "1"]);
+        assert_eq!(get_square_lines(&rendered, "ShortLn"), vec!["ShortLn", "P3 M", ""]);
+        assert_eq!(get_square_lines(&rendered, "Jail"), vec!["Jail", "", "[3]"]);
+        assert_eq!(get_square_lines(&rendered, "GO"), vec!["GO", "", "2"]);
+        assert_eq!(get_square_lines(&rendered, "Vermont"), vec!["Vermont", "$100", ""]);
+        assert!(rendered.contains(">1 Player 1: $1500 on Boardwalk"));
+        assert!(rendered.contains("3 Player 3: $1400 on Jail, in jail"));
+    }
+
+    #[test]
+    fn board_is_a_square_ring() {
+        let rendered = render_board(&get_new_board(), BorderStyleEnum::Ascii);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 45);
+        assert_eq!(lines[0].len(), 100);
+        assert_eq!(lines[44].len(), 100);
+        assert!(lines[1].starts_with("|Parking |"));
+        assert!(lines[41].ends_with("|GO      |"));
+    }
+
+    #[test]
+    fn unicode_borders_join_cleanly() {
+        let rendered = render_board(&get_new_board(), BorderStyleEnum::Unicode);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[0].starts_with("┌────────┬"));
+        assert!(lines[0].ends_with("┐"));
+        assert!(lines[4].starts_with("├────────┼"));
+        assert!(lines[8].starts_with("├────────┤"));
+        assert!(lines[44].starts_with("└"));
+        assert!(!rendered.contains('+'));
+    }
+}
@@ -0,0 +1,382 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use serde::{Deserialize, Serialize};
+use crate::board_state::BoardState;
+use crate::controller::{PlayerController, TurnActionEnum};
+use crate::dice::{DiceRoll, RollEngine, SeededDice, SpeedDieFace};
+use crate::jail::JailOptionEnum;
+use crate::location::LocationEnum;
+use crate::save::GameSnapshot;
+use crate::trade::TradeOffer;
+
+/// One roll or decision, along with the question it answered.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub enum ActionRecord {
+    Roll(DiceRoll),
+    SpeedDie(Option<SpeedDieFace>),
+    Buy { player_number: usize, location: LocationEnum, buy: bool },
+    Bid { player_number: usize, location: LocationEnum, highest_bid: i32, bid: Option<i32> },
+    JailOption { player_number: usize, choice: JailOptionEnum },
+    Destination { player_number: usize, location: LocationEnum },
+    BusMove { player_number: usize, options: Vec<i32>, spaces: i32 },
+    TurnActions { player_number: usize, actions: Vec<TurnActionEnum> },
+    TradeResponse { player_number: usize, offer: TradeOffer, accepted: bool },
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct TurnRecord {
+    pub actions: Vec<ActionRecord>,
+    /// The state the turn left behind, checked during replay.
+    pub snapshot: GameSnapshot,
+}
+
+/// A whole game as a starting state plus every roll and decision made since.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct ActionLog {
+    pub initial: GameSnapshot,
+    pub turns: Vec<TurnRecord>,
+}
+
+/// Where a replay first stopped matching its log.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Divergence {
+    /// Index into `ActionLog::turns`.
+    pub turn: usize,
+    pub reason: String,
+    pub expected: Box<GameSnapshot>,
+    pub actual: Box<GameSnapshot>,
+}
+
+type SharedActions = Rc<RefCell<VecDeque<ActionRecord>>>;
+
+struct RecordingRollEngine {
+    inner: Rc<RefCell<dyn RollEngine>>,
+    actions: SharedActions,
+}
+
+impl RollEngine for RecordingRollEngine {
+    fn roll(&mut self) -> DiceRoll {
+        let roll = self.inner.borrow_mut().roll();
+        self.actions.borrow_mut().push_back(ActionRecord::Roll(roll.clone()));
+        roll
+    }
+
+    fn roll_speed_die(&mut self) -> Option<SpeedDieFace> {
+        let face = self.inner.borrow_mut().roll_speed_die();
+        self.actions.borrow_mut().push_back(ActionRecord::SpeedDie(face));
+        face
+    }
+
+    fn save_state(&self) -> Option<SeededDice> {
+        self.inner.borrow().save_state()
+    }
+}
+
+struct RecordingController {
+    inner: Rc<RefCell<dyn PlayerController>>,
+    actions: SharedActions,
+}
+
+impl PlayerController for RecordingController {
+    fn should_buy(&mut self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        let buy = self.inner.borrow_mut().should_buy(board, player_number, location);
+        self.actions.borrow_mut().push_back(ActionRecord::Buy { player_number, location, buy });
+        buy
+    }
+
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        let bid = self.inner.borrow_mut().bid(board, player_number, location, highest_bid);
+        self.actions.borrow_mut().push_back(ActionRecord::Bid { player_number, location, highest_bid, bid });
+        bid
+    }
+
+    fn choose_jail_option(&mut self, board: &BoardState, player_number: usize, options: &[JailOptionEnum]) -> JailOptionEnum {
+        let choice = self.inner.borrow_mut().choose_jail_option(board, player_number, options);
+        self.actions.borrow_mut().push_back(ActionRecord::JailOption { player_number, choice });
+        choice
+    }
+
+    fn choose_destination(&mut self, board: &BoardState, player_number: usize) -> LocationEnum {
+        let location = self.inner.borrow_mut().choose_destination(board, player_number);
+        self.actions.borrow_mut().push_back(ActionRecord::Destination { player_number, location });
+        location
+    }
+
+    fn choose_bus_move(&mut self, board: &BoardState, player_number: usize, options: &[i32]) -> i32 {
+        let spaces = self.inner.borrow_mut().choose_bus_move(board, player_number, options);
+        self.actions.borrow_mut().push_back(ActionRecord::BusMove { player_number, options: options.to_vec(), spaces });
+        spaces
+    }
+
+    fn plan_turn_actions(&mut self, board: &BoardState, player_number: usize) -> Vec<TurnActionEnum> {
+        let actions = self.inner.borrow_mut().plan_turn_actions(board, player_number);
+        self.actions.borrow_mut().push_back(ActionRecord::TurnActions { player_number, actions: actions.clone() });
+        actions
+    }
+
+    fn respond_to_trade(&mut self, board: &BoardState, player_number: usize, offer: &TradeOffer) -> bool {
+        let accepted = self.inner.borrow_mut().respond_to_trade(board, player_number, offer);
+        self.actions.borrow_mut().push_back(ActionRecord::TradeResponse { player_number, offer: offer.clone(), accepted });
+        accepted
+    }
+}
+
+/// Records every roll and decision made on a board, turn by turn.
+pub struct GameRecorder {
+    actions: SharedActions,
+    log: ActionLog,
+}
+
+impl GameRecorder {
+    /// Wraps the board's dice and every player's controller so their answers are logged.
+    /// Controllers must be set before recording starts.
+    pub fn start(board: &mut BoardState) -> Self {
+        let actions: SharedActions = Rc::new(RefCell::new(VecDeque::new()));
+        board.roll_engine = Rc::new(RefCell::new(RecordingRollEngine { inner: board.roll_engine.clone(), actions: actions.clone() }));
+        let player_numbers: Vec<usize> = board.players.keys().copied().collect();
+        for player_number in player_numbers {
+            let inner = board.get_player_controller(player_number);
+            board.set_player_controller(player_number, Rc::new(RefCell::new(RecordingController { inner, actions: actions.clone() })));
+        }
+
+        Self {
+            actions,
+            log: ActionLog { initial: board.to_snapshot(), turns: Vec::new() },
+        }
+    }
+
+    pub fn take_turn(&mut self, board: &mut BoardState) {
+        board.take_turn();
+        let actions = self.actions.borrow_mut().drain(..).collect();
+        self.log.turns.push(TurnRecord { actions, snapshot: board.to_snapshot() });
+    }
+
+    pub fn finish(self) -> ActionLog {
+        self.log
+    }
+}
+
+/// Answers every question from the log, noting the first one that does not match.
+struct ScriptedPlayer {
+    actions: VecDeque<ActionRecord>,
+    mismatch: Option<String>,
+}
+
+impl ScriptedPlayer {
+    fn next(&mut self, expected: &str, matches: impl Fn(&ActionRecord) -> bool) -> Option<ActionRecord> {
+        let action = self.actions.pop_front();
+        match action {
+            Some(action) if matches(&action) => Some(action),
+            other => {
+                if self.mismatch.is_none() {
+                    self.mismatch = Some(format!("expected {} but the log has {:?}", expected, other));
+                }
+                None
+            },
+        }
+    }
+}
+
+struct ScriptedRollEngine(Rc<RefCell<ScriptedPlayer>>);
+
+impl RollEngine for ScriptedRollEngine {
+    fn roll(&mut self) -> DiceRoll {
+        match self.0.borrow_mut().next("a roll", |action| matches!(action, ActionRecord::Roll(_))) {
+            Some(ActionRecord::Roll(roll)) => roll,
+            _ => DiceRoll(1, 2),
+        }
+    }
+
+    fn roll_speed_die(&mut self) -> Option<SpeedDieFace> {
+        match self.0.borrow_mut().next("a speed die roll", |action| matches!(action, ActionRecord::SpeedDie(_))) {
+            Some(ActionRecord::SpeedDie(face)) => face,
+            _ => None,
+        }
+    }
+}
+
+struct ScriptedController(Rc<RefCell<ScriptedPlayer>>);
+
+impl PlayerController for ScriptedController {
+    fn should_buy(&mut self, _: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        let expected = format!("player {} deciding whether to buy {:?}", player_number, location);
+        match self.0.borrow_mut().next(&expected, |action| matches!(action,
+            ActionRecord::Buy { player_number: p, location: l, .. } if *p == player_number && *l == location)) {
+            Some(ActionRecord::Buy { buy, .. }) => buy,
+            _ => false,
+        }
+    }
+
+    fn bid(&mut self, _: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        let expected = format!("player {} bidding on {:?} over {}", player_number, location, highest_bid);
+        match self.0.borrow_mut().next(&expected, |action| matches!(action,
+            ActionRecord::Bid { player_number: p, location: l, highest_bid: h, .. } if *p == player_number && *l == location && *h == highest_bid)) {
+            Some(ActionRecord::Bid { bid, .. }) => bid,
+            _ => None,
+        }
+    }
+
+    fn choose_jail_option(&mut self, _: &BoardState, player_number: usize, _: &[JailOptionEnum]) -> JailOptionEnum {
+        let expected = format!("player {} choosing how to leave jail", player_number);
+        match self.0.borrow_mut().next(&expected, |action| matches!(action,
+            ActionRecord::JailOption { player_number: p, .. } if *p == player_number)) {
+            Some(ActionRecord::JailOption { choice, .. }) => choice,
+            _ => JailOptionEnum::RollForDoubles,
+        }
+    }
+
+    fn choose_destination(&mut self, _: &BoardState, player_number: usize) -> LocationEnum {
+        let expected = format!("player {} choosing where to go on triples", player_number);
+        match self.0.borrow_mut().next(&expected, |action| matches!(action,
+            ActionRecord::Destination { player_number: p, .. } if *p == player_number)) {
+            Some(ActionRecord::Destination { location, .. }) => location,
+            _ => LocationEnum::Go,
+        }
+    }
+
+    fn choose_bus_move(&mut self, _: &BoardState, player_number: usize, options: &[i32]) -> i32 {
+        let expected = format!("player {} choosing a Bus move from {:?}", player_number, options);
+        match self.0.borrow_mut().next(&expected, |action| matches!(action,
+            ActionRecord::BusMove { player_number: p, options: o, .. } if *p == player_number && o == options)) {
+            Some(ActionRecord::BusMove { spaces, .. }) => spaces,
+            _ => options[0],
+        }
+    }
+
+    fn plan_turn_actions(&mut self, _: &BoardState, player_number: usize) -> Vec<TurnActionEnum> {
+        let expected = format!("player {} planning turn actions", player_number);
+        match self.0.borrow_mut().next(&expected, |action| matches!(action,
+            ActionRecord::TurnActions { player_number: p, .. } if *p == player_number)) {
+            Some(ActionRecord::TurnActions { actions, .. }) => actions,
+            _ => Vec::new(),
+        }
+    }
+
+    fn respond_to_trade(&mut self, _: &BoardState, player_number: usize, offer: &TradeOffer) -> bool {
+        let expected = format!("player {} responding to {:?}", player_number, offer);
+        match self.0.borrow_mut().next(&expected, |action| matches!(action,
+            ActionRecord::TradeResponse { player_number: p, offer: o, .. } if *p == player_number && o == offer)) {
+            Some(ActionRecord::TradeResponse { accepted, .. }) => accepted,
+            _ => false,
+        }
+    }
+}
+
+/// Plays `log` back into a fresh board, checking the state after every turn. Returns the
+/// finished board, or the first turn where a question or the resulting state differed.
+pub fn replay(log: &ActionLog) -> Result<BoardState, Divergence> {
+    let mut board = BoardState::from_snapshot(log.initial.clone()).map_err(|error| Divergence {
+        turn: 0,
+        reason: error.to_string(),
+        expected: Box::new(log.initial.clone()),
+        actual: Box::new(log.initial.clone()),
+    })?;
+
+    let script = Rc::new(RefCell::new(ScriptedPlayer { actions: VecDeque::new(), mismatch: None }));
+    board.roll_engine = Rc::new(RefCell::new(ScriptedRollEngine(script.clone())));
+    let player_numbers: Vec<usize> = board.players.keys().copied().collect();
+    for player_number in player_numbers {
+        board.set_player_controller(player_number, Rc::new(RefCell::new(ScriptedController(script.clone()))));
+    }
+
+    for (turn, record) in log.turns.iter().enumerate() {
+        script.borrow_mut().actions = record.actions.iter().cloned().collect();
+        board.take_turn();
+
+        let actual = board.to_snapshot();
+        let mut script = script.borrow_mut();
+        let reason = match (script.mismatch.take(), script.actions.front()) {
+            (Some(mismatch), _) => Some(mismatch),
+            (None, Some(unused)) => Some(format!("the turn ended before {:?}", unused)),
+            (None, None) if !is_same_state(&actual, &record.snapshot) => Some("the resulting state differs".to_string()),
+            (None, None) => None,
+        };
+        if let Some(reason) = reason {
+            return Err(Divergence { turn, reason, expected: Box::new(record.snapshot.clone()), actual: Box::new(actual) });
+        }
+    }
+
+    Ok(board)
+}
+
+// Replays roll from the log rather than the dice, so the dice state is left out.
+fn is_same_state(actual: &GameSnapshot, expected: &GameSnapshot) -> bool {
+    GameSnapshot { dice: None, ..actual.clone() } == GameSnapshot { dice: None, ..expected.clone() }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::BoardState;
+    use crate::controller::{PlayerController, TurnActionEnum};
+    use crate::dice::DiceRoll;
+    use crate::replay::{replay, ActionLog, ActionRecord, GameRecorder};
+    use crate::rules::GameRules;
+
+    /// Builds houses on any monopoly it holds.
+    struct Builder;
+    impl PlayerController for Builder {
+        fn plan_turn_actions(&mut self, board: &BoardState, player_number: usize) -> Vec<TurnActionEnum> {
+            board.properties.get_monopolies(player_number).into_iter()
+                .flat_map(|group| group.get_locations())
+                .map(TurnActionEnum::BuyHouse)
+                .collect()
+        }
+    }
+
+    fn record_game(turns: usize, rules: GameRules) -> ActionLog {
+        let mut board = BoardState::new_seeded(99, rules);
+        board.seat_new_players(3);
+        board.set_player_controller(2, Rc::new(RefCell::new(Builder)));
+        let mut recorder = GameRecorder::start(&mut board);
+        for _ in 0..turns {
+            recorder.take_turn(&mut board);
+        }
+        recorder.finish()
+    }
+
+    #[test]
+    fn recorded_game_replays_exactly() {
+        let log = record_game(60, GameRules::default());
+        let board = match replay(&log) {
+            Ok(board) => board,
+            Err(divergence) => panic!("Replay diverged on turn {}: {}", divergence.turn, divergence.reason),
+        };
+        assert_eq!(board.to_snapshot().players, log.turns.last().unwrap().snapshot.players);
+
+        let json = serde_json::to_string(&log).unwrap();
+        let loaded: ActionLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, log);
+        assert!(replay(&loaded).is_ok());
+    }
+
+    #[test]
+    fn replay_reports_the_first_divergence() {
+        let mut log = record_game(20, GameRules::default());
+        let roll = log.turns[7].actions.iter_mut()
+            .find_map(|action| match action {
+                ActionRecord::Roll(roll) => Some(roll),
+                _ => None,
+            })
+            .unwrap();
+        *roll = if *roll == DiceRoll(1, 2) { DiceRoll(2, 4) } else { DiceRoll(1, 2) };
+
+        let divergence = match replay(&log) {
+            Err(divergence) => divergence,
+            Ok(_) => panic!("The tampered log replayed cleanly!"),
+        };
+        assert_eq!(divergence.turn, 7);
+        assert_ne!(divergence.expected, divergence.actual);
+    }
+
+    #[test]
+    fn speed_die_games_replay_exactly() {
+        let log = record_game(60, GameRules { speed_die: true, ..GameRules::default() });
+        assert!(log.turns.iter().flat_map(|turn| &turn.actions).any(|action| matches!(action, ActionRecord::SpeedDie(Some(_)))));
+        if let Err(divergence) = replay(&log) {
+            panic!("Replay diverged on turn {}: {}", divergence.turn, divergence.reason);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::board_state::{BANK_HOTELS, BANK_HOUSES};
+use crate::error::GameError;
+use crate::player::{GO_SALARY, JAIL_FINE, JAIL_ROLL_ATTEMPTS, STARTING_CASH};
+
+/// House rules for a game. Anything left out of a rules file keeps the standard value.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameRules {
+    pub starting_cash: i32,
+    pub go_salary: i32,
+    /// Pay the salary twice to a player whose move ends exactly on Go.
+    pub double_salary_on_go: bool,
+    /// Taxes and card fines go into a pot that whoever lands on Free Parking collects.
+    pub free_parking_jackpot: bool,
+    /// What the bank puts in the pot at the start and after every collection.
+    pub free_parking_seed: i32,
+    /// Owners sitting in jail collect no rent.
+    pub no_rent_in_jail: bool,
+    /// Put deeds up for auction when the player who landed on them declines, and when the bank
+    /// takes them back from a bankrupt player. Without auctions they simply stay unowned.
+    pub auctions: bool,
+    /// Houses and hotels in the bank. Building stops when they run out.
+    pub bank_houses: i32,
+    pub bank_hotels: i32,
+    pub jail_fine: i32,
+    /// Rolls for doubles allowed before the fine must be paid.
+    pub jail_roll_attempts: i32,
+    /// Roll the Mega Edition speed die alongside the white dice when not in jail.
+    pub speed_die: bool,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            starting_cash: STARTING_CASH,
+            go_salary: GO_SALARY,
+            double_salary_on_go: false,
+            free_parking_jackpot: false,
+            free_parking_seed: 0,
+            no_rent_in_jail: false,
+            auctions: true,
+            bank_houses: BANK_HOUSES,
+            bank_hotels: BANK_HOTELS,
+            jail_fine: JAIL_FINE,
+            jail_roll_attempts: JAIL_ROLL_ATTEMPTS,
+            speed_die: false,
+        }
+    }
+}
+
+impl GameRules {
+    pub fn from_json(json: &str) -> Result<Self, GameError> {
+        let rules: GameRules = serde_json::from_str(json).map_err(|error| GameError::InvalidRules(error.to_string()))?;
+        rules.validate()
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, GameError> {
+        let rules: GameRules = toml::from_str(text).map_err(|error| GameError::InvalidRules(error.to_string()))?;
+        rules.validate()
+    }
+
+    /// Reads a `.json` file as JSON and anything else as TOML.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        let text = fs::read_to_string(path).map_err(|error| GameError::InvalidRules(format!("{}: {}", path.display(), error)))?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => GameRules::from_json(&text),
+            _ => GameRules::from_toml(&text),
+        }
+    }
+
+    fn validate(self) -> Result<Self, GameError> {
+        let amounts = [
+            ("starting_cash", self.starting_cash),
+            ("go_salary", self.go_salary),
+            ("free_parking_seed", self.free_parking_seed),
+            ("bank_houses", self.bank_houses),
+            ("bank_hotels", self.bank_hotels),
+            ("jail_fine", self.jail_fine),
+        ];
+        if let Some((name, _)) = amounts.iter().find(|(_, amount)| *amount < 0) {
+            return Err(GameError::InvalidRules(format!("{} cannot be negative", name)));
+        }
+        if self.jail_roll_attempts < 1 {
+            return Err(GameError::InvalidRules("jail_roll_attempts must be at least 1".to_string()));
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Player;
+    use crate::dice::DiceRoll;
+    use crate::error::GameError;
+    use crate::location::LocationEnum;
+    use crate::rules::GameRules;
+    use crate::test_support::get_board_with_rules;
+
+    #[test]
+    fn rules_load_from_toml_and_json_with_defaults() {
+        let rules = GameRules::from_toml("starting_cash = 2000\ndouble_salary_on_go = true\n").unwrap();
+        assert_eq!(rules, GameRules { starting_cash: 2000, double_salary_on_go: true, ..GameRules::default() });
+
+        let rules = GameRules::from_toml("free_parking_jackpot = true\nfree_parking_seed = 500\n").unwrap();
+        assert_eq!(rules, GameRules { free_parking_jackpot: true, free_parking_seed: 500, ..GameRules::default() });
+
+        let rules = GameRules::from_json(r#"{ "auctions": false, "jail_fine": 100 }"#).unwrap();
+        assert_eq!(rules, GameRules { auctions: false, jail_fine: 100, ..GameRules::default() });
+
+        assert!(matches!(GameRules::from_toml("jail_roll_attempts = 0"), Err(GameError::InvalidRules(_))));
+        assert!(matches!(GameRules::from_toml("go_salary = \"lots\""), Err(GameError::InvalidRules(_))));
+        assert!(matches!(GameRules::from_json(r#"{ "starting_cash": -5 }"#), Err(GameError::InvalidRules(_))));
+    }
+
+    #[test]
+    fn players_start_with_the_rules_cash_and_salary() {
+        let rules = GameRules { starting_cash: 1000, go_salary: 400, double_salary_on_go: true, ..GameRules::default() };
+        let mut board = get_board_with_rules(DiceRoll(2, 3), 2, rules);
+        assert_eq!(board.players[&1].cash, 1000);
+        board.add_player(Player { cash: 42, ..Player::new(3, &board.rules) });
+        assert_eq!(board.players[&3].cash, 42);
+
+        board.buy_property(1, LocationEnum::MediterraneanAvenue).unwrap();
+        board.get_current_player_by_number(1).current_location = LocationEnum::Chance3;
+        board.get_current_player_by_number(1).cash = 500;
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::MediterraneanAvenue);
+        assert_eq!(board.players[&1].cash, 900);
+
+        board.get_current_player_by_number(2).current_location = LocationEnum::ShortLine;
+        board.get_current_player_by_number(2).cash = 0;
+        board.take_turn();
+        assert_eq!(board.players[&2].current_location, LocationEnum::Go);
+        assert_eq!(board.players[&2].cash, 800);
+    }
+
+    #[test]
+    fn owners_in_jail_collect_no_rent() {
+        let rules = GameRules { no_rent_in_jail: true, ..GameRules::default() };
+        let mut board = get_board_with_rules(DiceRoll(1, 2), 2, rules);
+        board.buy_property(2, LocationEnum::BalticAvenue).unwrap();
+        board.get_current_player_by_number(2).send_to_jail();
+        board.take_turn();
+        assert_eq!(board.players[&1].cash, 1500);
+        assert_eq!(board.players[&2].cash, 1440);
+    }
+
+    #[test]
+    fn declined_deeds_stay_unowned_without_auctions() {
+        let rules = GameRules { auctions: false, ..GameRules::default() };
+        let mut board = get_board_with_rules(DiceRoll(1, 2), 2, rules);
+        board.get_current_player_by_number(1).cash = 10;
+        board.take_turn();
+        assert_eq!(board.properties.get_property_ref(LocationEnum::BalticAvenue).owned_by_player_number, None);
+    }
+
+    #[test]
+    fn jail_fine_and_attempts_follow_the_rules() {
+        let rules = GameRules { jail_fine: 20, jail_roll_attempts: 1, bank_houses: 5, ..GameRules::default() };
+        let mut board = get_board_with_rules(DiceRoll(1, 2), 2, rules);
+        assert_eq!(board.houses_available, 5);
+        board.get_current_player_by_number(1).send_to_jail();
+        board.take_turn();
+        assert!(!board.players[&1].is_in_jail);
+        assert_eq!(board.players[&1].current_location, LocationEnum::StateAvenue);
+        assert_eq!(board.players[&1].cash, 1500 - 20 - 140);
+    }
+}
@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use serde::{Deserialize, Serialize};
+use crate::board_state::BoardState;
+use crate::cards::{ChanceCardEnum, CommunityChestCardEnum, Deck};
+use crate::dice::{Dice, RollEngine, SeededDice};
+use crate::error::GameError;
+use crate::location::LocationEnum;
+use crate::rules::GameRules;
+use crate::Player;
+
+/// The parts of a deed that change during a game.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct PropertySnapshot {
+    pub location: LocationEnum,
+    pub owned_by_player_number: Option<usize>,
+    pub houses: i32,
+    pub hotels: i32,
+    pub is_mortgaged: bool,
+}
+
+/// Everything needed to resume a game exactly where it stopped. Controllers, subscribers and
+/// custom square actions are code rather than state, so they are set up again after loading.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub player_turn: usize,
+    pub players: Vec<Player>,
+    pub properties: Vec<PropertySnapshot>,
+    pub houses_available: i32,
+    pub hotels_available: i32,
+    #[serde(default)]
+    pub free_parking_pot: i32,
+    pub chance_deck: Vec<ChanceCardEnum>,
+    pub community_chest_deck: Vec<CommunityChestCardEnum>,
+    pub seed: Option<u64>,
+    /// Seeded dice mid-sequence. Games rolled with unseeded dice resume with fresh ones.
+    pub dice: Option<SeededDice>,
+    /// Saves from before house rules existed load with the standard ones.
+    #[serde(default)]
+    pub rules: GameRules,
+}
+
+impl BoardState {
+    pub fn to_snapshot(&self) -> GameSnapshot {
+        let mut players: Vec<Player> = self.players.values().cloned().collect();
+        players.sort_by_key(|player| player.player_number);
+        let mut properties: Vec<PropertySnapshot> = self.properties.list.values()
+            .filter(|property| property.is_purchasable())
+            .map(|property| PropertySnapshot {
+                location: property.location,
+                owned_by_player_number: property.owned_by_player_number,
+                houses: property.houses,
+                hotels: property.hotels,
+                is_mortgaged: property.is_mortgaged,
+            })
+            .collect();
+        properties.sort_by_key(|property| property.location);
+
+        GameSnapshot {
+            player_turn: self.player_turn,
+            players,
+            properties,
+            houses_available: self.houses_available,
+            hotels_available: self.hotels_available,
+            free_parking_pot: self.free_parking_pot,
+            chance_deck: self.chance_deck.cards.iter().copied().collect(),
+            community_chest_deck: self.community_chest_deck.cards.iter().copied().collect(),
+            seed: self.seed,
+            dice: self.roll_engine.borrow().save_state(),
+            rules: self.rules.clone(),
+        }
+    }
+
+    pub fn from_snapshot(snapshot: GameSnapshot) -> Result<Self, GameError> {
+        let roll_engine: Rc<RefCell<dyn RollEngine>> = match snapshot.dice {
+            Some(dice) => Rc::new(RefCell::new(dice)),
+            None => Rc::new(RefCell::new(Dice{})),
+        };
+        let mut board = BoardState::new(roll_engine, snapshot.rules);
+
+        for (index, player) in snapshot.players.into_iter().enumerate() {
+            if player.player_number != index + 1 {
+                return Err(GameError::InvalidSave(format!("player {} is out of order", player.player_number)));
+            }
+            board.players.insert(player.player_number, player);
+        }
+        if !board.players.contains_key(&snapshot.player_turn) {
+            return Err(GameError::InvalidSave(format!("player {} cannot take the turn", snapshot.player_turn)));
+        }
+
+        for saved in snapshot.properties {
+            if !saved.location.is_purchasable() {
+                return Err(GameError::InvalidSave(format!("{:?} is not a deed", saved.location)));
+            }
+            if saved.owned_by_player_number.is_some_and(|owner| !board.players.contains_key(&owner)) {
+                return Err(GameError::InvalidSave(format!("{:?} belongs to a missing player", saved.location)));
+            }
+            let property = board.properties.get_property(saved.location);
+            property.owned_by_player_number = saved.owned_by_player_number;
+            property.houses = saved.houses;
+            property.hotels = saved.hotels;
+            property.is_mortgaged = saved.is_mortgaged;
+        }
+
+        board.player_turn = snapshot.player_turn;
+        board.houses_available = snapshot.houses_available;
+        board.hotels_available = snapshot.hotels_available;
+        board.free_parking_pot = snapshot.free_parking_pot;
+        board.chance_deck = Deck::new(snapshot.chance_deck);
+        board.community_chest_deck = Deck::new(snapshot.community_chest_deck);
+        board.seed = snapshot.seed;
+        Ok(board)
+    }
+
+    pub fn save_to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_snapshot()).expect("Game state always serializes!")
+    }
+
+    pub fn load_from_json(json: &str) -> Result<Self, GameError> {
+        let snapshot = serde_json::from_str(json).map_err(|error| GameError::InvalidSave(error.to_string()))?;
+        BoardState::from_snapshot(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::BoardState;
+    use crate::cards::DeckEnum;
+    use crate::error::GameError;
+    use crate::location::{ColorGroupEnum, LocationEnum};
+    use crate::rules::GameRules;
+
+    #[test]
+    fn saved_game_round_trips_through_json() {
+        let mut board = BoardState::new_seeded(11, GameRules::default());
+        board.seat_new_players(3);
+        for location in ColorGroupEnum::DarkBlue.get_locations() {
+            board.buy_property(2, location).unwrap();
+        }
+        board.buy_house(2, LocationEnum::Boardwalk).unwrap();
+        board.buy_property(3, LocationEnum::ShortLine).unwrap();
+        board.mortgage_property(3, LocationEnum::ShortLine).unwrap();
+        board.get_current_player_by_number(1).get_out_of_jail_free_cards.push(DeckEnum::Chance);
+        for _ in 0..5 {
+            board.take_turn();
+        }
+
+        let json = board.save_to_json();
+        let loaded = BoardState::load_from_json(&json).unwrap();
+        assert_eq!(loaded.to_snapshot(), board.to_snapshot());
+        assert_eq!(loaded.save_to_json(), json);
+    }
+
+    #[test]
+    fn loaded_game_continues_with_the_same_rolls() {
+        let mut board = BoardState::new_seeded(5, GameRules::default());
+        board.seat_new_players(2);
+        board.take_turn();
+        let loaded = BoardState::load_from_json(&board.save_to_json()).unwrap();
+        for _ in 0..3 {
+            assert_eq!(loaded.roll_engine.borrow_mut().roll(), board.roll_engine.borrow_mut().roll());
+        }
+    }
+
+    #[test]
+    fn invalid_saves_are_rejected() {
+        assert!(matches!(BoardState::load_from_json("{"), Err(GameError::InvalidSave(_))));
+
+        let mut board = BoardState::new_seeded(5, GameRules::default());
+        board.seat_new_players(2);
+        let mut snapshot = board.to_snapshot();
+        snapshot.properties[0].owned_by_player_number = Some(9);
+        assert!(matches!(BoardState::from_snapshot(snapshot), Err(GameError::InvalidSave(_))));
+    }
+}
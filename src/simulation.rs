@@ -0,0 +1,311 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::path::Path;
+use std::rc::Rc;
+use crate::board_state::BoardState;
+use crate::controller::{DefaultController, PlayerController};
+use crate::rules::GameRules;
+use crate::strategy::{get_strategy, DEFAULT_CASH_RESERVE, STRATEGY_NAMES};
+
+pub const DEFAULT_MAX_TURNS: usize = 1000;
+
+/// A strategy taking part in a simulation. Each game gets a fresh controller from
+/// `new_controller`, so strategies can keep state without it leaking between games.
+#[derive(Clone)]
+pub struct Contestant {
+    pub name: String,
+    pub new_controller: Rc<dyn Fn() -> Rc<RefCell<dyn PlayerController>>>,
+}
+
+impl Contestant {
+    pub fn new(name: &str, new_controller: impl Fn() -> Rc<RefCell<dyn PlayerController>> + 'static) -> Self {
+        Self { name: name.to_string(), new_controller: Rc::new(new_controller) }
+    }
+
+    pub fn default_controller() -> Self {
+        Contestant::new("default", || Rc::new(RefCell::new(DefaultController)))
+    }
+}
+
+#[derive(Clone)]
+pub struct SimulationConfig {
+    pub contestants: Vec<Contestant>,
+    pub games: usize,
+    /// Game `n` is dealt and rolled from `seed + n`.
+    pub seed: u64,
+    /// Games still going after this many turns are stopped without a winner.
+    pub max_turns: usize,
+    pub rules: GameRules,
+}
+
+impl SimulationConfig {
+    pub fn new(contestants: Vec<Contestant>, games: usize, seed: u64) -> Self {
+        Self { contestants, games, seed, max_turns: DEFAULT_MAX_TURNS, rules: GameRules::default() }
+    }
+}
+
+/// How one game went. Players are identified by their index in the contestant list rather than
+/// their seat, since seats rotate from game to game.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct GameResult {
+    pub seed: u64,
+    pub winner: Option<usize>,
+    pub turns: usize,
+    /// The turn each contestant went bankrupt on, if they did.
+    pub bankruptcy_turns: Vec<Option<usize>>,
+    /// Each contestant's cash after every turn.
+    pub cash: Vec<Vec<i32>>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ContestantReport {
+    pub name: String,
+    pub wins: usize,
+    pub win_rate: f64,
+    /// Every turn this contestant went bankrupt on, one entry per bankruptcy, in ascending order.
+    pub bankruptcy_turns: Vec<usize>,
+    /// Average cash after each turn. Finished games count with the cash they ended on.
+    pub cash_trajectory: Vec<f64>,
+}
+
+impl ContestantReport {
+    /// Bankruptcies counted in buckets of `bucket_size` turns, starting from turn 1.
+    pub fn get_bankruptcy_histogram(&self, bucket_size: usize) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        for turn in &self.bankruptcy_turns {
+            let bucket = (turn - 1) / bucket_size.max(1);
+            if histogram.len() <= bucket {
+                histogram.resize(bucket + 1, 0);
+            }
+            histogram[bucket] += 1;
+        }
+        histogram
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct SimulationReport {
+    pub games: usize,
+    pub unfinished_games: usize,
+    pub average_game_length: f64,
+    pub contestants: Vec<ContestantReport>,
+}
+
+/// Plays one game to the end, or to `max_turns`. `rotation` shifts which contestant sits in
+/// the first seat, so no strategy always goes first.
+pub fn simulate_game(contestants: &[Contestant], rules: &GameRules, seed: u64, rotation: usize, max_turns: usize) -> GameResult {
+    let count = contestants.len();
+    let seat_of = |contestant: usize| (contestant + count - rotation % count) % count + 1;
+
+    let mut board = BoardState::new_seeded(seed, rules.clone());
+    board.seat_new_players(count as i32);
+    for seat in 0..count {
+        let contestant = &contestants[(seat + rotation) % count];
+        board.get_current_player_by_number(seat + 1).name = contestant.name.clone();
+        board.set_player_controller(seat + 1, (contestant.new_controller)());
+    }
+
+    let mut bankruptcy_turns = vec![None; count];
+    let mut cash = vec![Vec::new(); count];
+    let mut turns = 0;
+    while !board.is_game_over() && turns < max_turns {
+        board.take_turn();
+        turns += 1;
+        for contestant in 0..count {
+            let player = &board.players[&seat_of(contestant)];
+            if player.is_bankrupt && bankruptcy_turns[contestant].is_none() {
+                bankruptcy_turns[contestant] = Some(turns);
+            }
+            cash[contestant].push(player.cash);
+        }
+    }
+
+    let winner = board.get_winner().map(|seat| (seat - 1 + rotation) % count);
+    GameResult { seed, winner, turns, bankruptcy_turns, cash }
+}
+
+/// Plays every game in `config` and sums up how each contestant did.
+pub fn run_simulation(config: &SimulationConfig) -> SimulationReport {
+    let results: Vec<GameResult> = (0..config.games)
+        .map(|game| simulate_game(&config.contestants, &config.rules, config.seed.wrapping_add(game as u64), game, config.max_turns))
+        .collect();
+    summarize(config, &results)
+}
+
+fn summarize(config: &SimulationConfig, results: &[GameResult]) -> SimulationReport {
+    let games = results.len();
+    let longest = results.iter().map(|result| result.turns).max().unwrap_or(0);
+    let total_turns: usize = results.iter().map(|result| result.turns).sum();
+
+    let contestants = config.contestants.iter().enumerate()
+        .map(|(index, contestant)| {
+            let wins = results.iter().filter(|result| result.winner == Some(index)).count();
+            let mut bankruptcy_turns: Vec<usize> = results.iter()
+                .filter_map(|result| result.bankruptcy_turns[index])
+                .collect();
+            bankruptcy_turns.sort();
+            let cash_trajectory = (0..longest)
+                .map(|turn| {
+                    let total: i64 = results.iter()
+                        .filter_map(|result| result.cash[index].get(turn).or(result.cash[index].last()))
+                        .map(|cash| *cash as i64)
+                        .sum();
+                    total as f64 / games as f64
+                })
+                .collect();
+            ContestantReport {
+                name: contestant.name.clone(),
+                wins,
+                win_rate: if games == 0 { 0.0 } else { wins as f64 / games as f64 },
+                bankruptcy_turns,
+                cash_trajectory,
+            }
+        })
+        .collect();
+
+    SimulationReport {
+        games,
+        unfinished_games: results.iter().filter(|result| result.winner.is_none()).count(),
+        average_game_length: if games == 0 { 0.0 } else { total_turns as f64 / games as f64 },
+        contestants,
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} games, {} unfinished, {:.1} turns on average", self.games, self.unfinished_games, self.average_game_length)?;
+        for contestant in &self.contestants {
+            let median = contestant.bankruptcy_turns.get(contestant.bankruptcy_turns.len() / 2);
+            let bankruptcies = match median {
+                Some(turn) => format!("{} bankruptcies, median turn {}", contestant.bankruptcy_turns.len(), turn),
+                None => "never bankrupt".to_string(),
+            };
+            let final_cash = contestant.cash_trajectory.last().copied().unwrap_or(0.0);
+            writeln!(f, "{}: {} wins ({:.1}%), {}, ${:.0} average final cash",
+                contestant.name, contestant.wins, contestant.win_rate * 100.0, bankruptcies, final_cash)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads `simulate` options: `--games N`, `--seed N`, `--max-turns N`, `--rules FILE`, and
+/// either `--players N` default players or one `--strategy NAME` per player, each keeping
+/// `--reserve N` cash in hand.
+pub fn parse_args(args: &[String]) -> Result<SimulationConfig, String> {
+    let mut config = SimulationConfig::new(Vec::new(), 100, 0);
+    let mut players = 2;
+    let mut strategies: Vec<String> = Vec::new();
+    let mut cash_reserve = DEFAULT_CASH_RESERVE;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(format!("{} needs a value", flag))?;
+        if flag == "--strategy" {
+            strategies.push(value.clone());
+            continue;
+        }
+        if flag == "--rules" {
+            config.rules = GameRules::load(Path::new(value)).map_err(|error| error.to_string())?;
+            continue;
+        }
+        let number: u64 = value.parse().map_err(|_| format!("{} is not a number", value))?;
+        match flag.as_str() {
+            "--games" => config.games = number as usize,
+            "--seed" => config.seed = number,
+            "--players" => players = number as usize,
+            "--max-turns" => config.max_turns = number as usize,
+            "--reserve" => cash_reserve = number as i32,
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+
+    config.contestants = if strategies.is_empty() {
+        vec![Contestant::default_controller(); players]
+    } else {
+        strategies.iter()
+            .map(|name| get_strategy(name, cash_reserve)
+                .ok_or(format!("unknown strategy {}, expected one of {}", name, STRATEGY_NAMES.join(", "))))
+            .collect::<Result<Vec<Contestant>, String>>()?
+    };
+    if !(2..=8).contains(&config.contestants.len()) {
+        return Err("a game needs 2 to 8 players".to_string());
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::board_state::BoardState;
+    use crate::controller::PlayerController;
+    use crate::location::LocationEnum;
+    use crate::rules::GameRules;
+    use crate::simulation::{parse_args, run_simulation, simulate_game, Contestant, SimulationConfig};
+
+    struct NeverBuys;
+    impl PlayerController for NeverBuys {
+        fn should_buy(&mut self, _board: &BoardState, _player_number: usize, _location: LocationEnum) -> bool {
+            false
+        }
+
+        fn bid(&mut self, _board: &BoardState, _player_number: usize, _location: LocationEnum, _highest_bid: i32) -> Option<i32> {
+            None
+        }
+    }
+
+    fn get_contestants() -> Vec<Contestant> {
+        vec![
+            Contestant::default_controller(),
+            Contestant::new("never buys", || Rc::new(RefCell::new(NeverBuys))),
+        ]
+    }
+
+    #[test]
+    fn simulation_is_repeatable() {
+        let mut config = SimulationConfig::new(get_contestants(), 6, 3);
+        config.max_turns = 300;
+        let report = run_simulation(&config);
+        assert_eq!(report, run_simulation(&config));
+        assert_eq!(report.games, 6);
+        assert_eq!(report.contestants[0].wins + report.contestants[1].wins + report.unfinished_games, 6);
+        assert!(report.average_game_length > 0.0 && report.average_game_length <= 300.0);
+        assert!(report.contestants.iter().all(|contestant| contestant.cash_trajectory.len() <= 300));
+    }
+
+    #[test]
+    fn winner_and_bankruptcies_follow_the_contestant_across_seats() {
+        let contestants = get_contestants();
+        for rotation in 0..2 {
+            let result = simulate_game(&contestants, &GameRules::default(), 42, rotation, 2000);
+            assert_eq!(result.cash[0].len(), result.turns);
+            if let Some(winner) = result.winner {
+                let loser = 1 - winner;
+                assert_eq!(result.bankruptcy_turns[winner], None);
+                assert!(result.bankruptcy_turns[loser].is_some_and(|turn| turn <= result.turns));
+                assert!(*result.cash[loser].last().unwrap() <= 0);
+            }
+        }
+    }
+
+    #[test]
+    fn bankruptcy_histogram_buckets_turns() {
+        let config = SimulationConfig::new(get_contestants(), 0, 0);
+        let mut report = run_simulation(&config);
+        report.contestants[0].bankruptcy_turns = vec![1, 10, 11, 35];
+        assert_eq!(report.contestants[0].get_bankruptcy_histogram(10), vec![2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn args_configure_the_simulation() {
+        let args: Vec<String> = ["--games", "5", "--players", "3", "--seed", "9"].iter().map(|arg| arg.to_string()).collect();
+        let config = parse_args(&args).unwrap();
+        assert_eq!((config.games, config.seed, config.contestants.len()), (5, 9, 3));
+        assert!(parse_args(&["--players".to_string(), "1".to_string()]).is_err());
+        assert!(parse_args(&["--speed".to_string(), "1".to_string()]).is_err());
+
+        let args: Vec<String> = ["--strategy", "orange-red", "--strategy", "never-buy", "--reserve", "50"].iter().map(|arg| arg.to_string()).collect();
+        let names: Vec<String> = parse_args(&args).unwrap().contestants.into_iter().map(|contestant| contestant.name).collect();
+        assert_eq!(names, vec!["orange-red", "never-buy"]);
+        assert!(parse_args(&["--strategy".to_string(), "random".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::board_state::BoardState;
+use crate::dice::{DiceRoll, Roll, SpeedDieFace};
+use crate::events::GameEvent;
+use crate::location::{LocationEnum, Property, BOARD_SIZE};
+
+impl BoardState {
+    /// The first deed past `from` that nobody owns, going round the board.
+    pub fn get_next_unowned_deed(&self, from: LocationEnum) -> Option<LocationEnum> {
+        self.find_next_square(from, |property| property.is_purchasable() && property.owned_by_player_number.is_none())
+    }
+
+    /// The first deed past `from` where `player_number` would owe rent.
+    pub fn get_next_rent_due(&self, player_number: usize, from: LocationEnum) -> Option<LocationEnum> {
+        self.find_next_square(from, |property| property.is_purchasable() && !property.is_mortgaged
+            && property.owned_by_player_number.is_some_and(|owner| owner != player_number))
+    }
+
+    fn find_next_square(&self, from: LocationEnum, matches: impl Fn(&Property) -> bool) -> Option<LocationEnum> {
+        (1..BOARD_SIZE)
+            .map(|spaces| from.advance(spaces))
+            .find(|location| matches(self.properties.get_property_ref(*location)))
+    }
+
+    pub(crate) fn roll_speed_die(&mut self, player_number: usize) -> Option<SpeedDieFace> {
+        let face = self.roll_engine.borrow_mut().roll_speed_die();
+        if let Some(face) = face {
+            self.emit(GameEvent::SpeedDieRolled { player_number, face });
+        }
+        face
+    }
+
+    /// The spaces `roll` moves, asking the controller which die to use when the Bus shows.
+    pub(crate) fn get_speed_die_move(&mut self, player_number: usize, roll: &Roll) -> i32 {
+        if roll.speed_die != Some(SpeedDieFace::Bus) {
+            return roll.total();
+        }
+
+        let options = roll.get_bus_options();
+        let controller = self.get_player_controller(player_number);
+        let spaces = controller.borrow_mut().choose_bus_move(self, player_number, &options);
+        // A move the Bus does not offer falls back to both white dice.
+        if options.contains(&spaces) { spaces } else { roll.dice.total() }
+    }
+
+    /// Triples: the player goes straight to any square they like.
+    pub(crate) fn move_anywhere(&mut self, player_number: usize, dice: &DiceRoll) {
+        let controller = self.get_player_controller(player_number);
+        let destination = controller.borrow_mut().choose_destination(self, player_number);
+        self.move_player_to(player_number, destination, dice);
+    }
+
+    /// Mr. Monopoly: once the move is settled, go on to the next unowned deed, or to the next
+    /// deed where rent is due once every deed has been sold.
+    pub(crate) fn follow_mr_monopoly(&mut self, player_number: usize, dice: &DiceRoll) {
+        let player = &self.players[&player_number];
+        if player.is_bankrupt || player.is_in_jail {
+            return;
+        }
+
+        let location = player.current_location;
+        let destination = self.get_next_unowned_deed(location)
+            .or_else(|| self.get_next_rent_due(player_number, location));
+        if let Some(destination) = destination {
+            self.move_player_to(player_number, destination, dice);
+        }
+    }
+
+    fn move_player_to(&mut self, player_number: usize, destination: LocationEnum, dice: &DiceRoll) {
+        let rules = self.rules.clone();
+        let player = self.get_current_player_by_number(player_number);
+        let last_location = player.current_location;
+        player.advance_to(destination, &rules);
+        self.record_move(player_number, last_location);
+        self.settle_bank_debt(player_number);
+        self.resolve_landing(destination, dice);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::BoardState;
+    use crate::controller::PlayerController;
+    use crate::dice::{DiceRoll, SpeedDieFace};
+    use crate::events::GameEvent;
+    use crate::location::LocationEnum;
+    use crate::rules::GameRules;
+    use crate::test_support::{get_board_with_rules, FixedSpeedDice};
+
+    /// Heads for `destination` on triples and takes the shortest Bus ride.
+    struct Traveller {
+        destination: LocationEnum,
+    }
+
+    impl PlayerController for Traveller {
+        fn choose_destination(&mut self, _: &BoardState, _: usize) -> LocationEnum {
+            self.destination
+        }
+
+        fn choose_bus_move(&mut self, _: &BoardState, _: usize, options: &[i32]) -> i32 {
+            options.iter().copied().min().unwrap()
+        }
+    }
+
+    fn get_new_board(roll: DiceRoll, face: SpeedDieFace, speed_die: bool) -> BoardState {
+        let mut board = get_board_with_rules(roll.clone(), 2, GameRules { speed_die, ..GameRules::default() });
+        board.roll_engine = Rc::new(RefCell::new(FixedSpeedDice(roll, face)));
+        board
+    }
+
+    #[test]
+    fn speed_die_number_adds_to_the_move() {
+        let mut board = get_new_board(DiceRoll(1, 2), SpeedDieFace::Three, true);
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::OrientalAvenue);
+        assert!(board.get_events().contains(&GameEvent::SpeedDieRolled { player_number: 1, face: SpeedDieFace::Three }));
+        assert_eq!(board.player_turn, 2);
+
+        let mut board = get_new_board(DiceRoll(1, 2), SpeedDieFace::Three, false);
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::BalticAvenue);
+        assert!(!board.get_events().iter().any(|event| matches!(event, GameEvent::SpeedDieRolled { .. })));
+    }
+
+    #[test]
+    fn bus_moves_by_the_chosen_die() {
+        let mut board = get_new_board(DiceRoll(1, 4), SpeedDieFace::Bus, true);
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::ReadingRailroad);
+
+        let mut board = get_new_board(DiceRoll(1, 4), SpeedDieFace::Bus, true);
+        board.set_player_controller(1, Rc::new(RefCell::new(Traveller { destination: LocationEnum::Go })));
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::MediterraneanAvenue);
+    }
+
+    #[test]
+    fn triples_move_anywhere_and_end_the_turn() {
+        let mut board = get_new_board(DiceRoll(2, 2), SpeedDieFace::Two, true);
+        board.set_player_controller(1, Rc::new(RefCell::new(Traveller { destination: LocationEnum::Boardwalk })));
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::Boardwalk);
+        assert_eq!(board.properties.get_property_ref(LocationEnum::Boardwalk).owned_by_player_number, Some(1));
+        assert_eq!(board.players[&1].doubles_roll_count, 0);
+        assert_eq!(board.player_turn, 2);
+    }
+
+    #[test]
+    fn mr_monopoly_advances_to_the_next_unowned_deed() {
+        let mut board = get_new_board(DiceRoll(1, 2), SpeedDieFace::MrMonopoly, true);
+        board.properties.get_property(LocationEnum::ReadingRailroad).owned_by_player_number = Some(2);
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::OrientalAvenue);
+        assert_eq!(board.properties.get_property_ref(LocationEnum::BalticAvenue).owned_by_player_number, Some(1));
+        assert_eq!(board.properties.get_property_ref(LocationEnum::OrientalAvenue).owned_by_player_number, Some(1));
+    }
+
+    #[test]
+    fn mr_monopoly_collects_rent_once_every_deed_is_sold() {
+        let mut board = get_new_board(DiceRoll(1, 2), SpeedDieFace::MrMonopoly, true);
+        for square in 1..=40 {
+            let location = LocationEnum::try_from(square).unwrap();
+            if location.is_purchasable() {
+                board.properties.get_property(location).owned_by_player_number = Some(2);
+            }
+        }
+        board.properties.get_property(LocationEnum::ReadingRailroad).is_mortgaged = true;
+        board.take_turn();
+
+        assert_eq!(board.players[&1].current_location, LocationEnum::OrientalAvenue);
+        let rent_paid: Vec<LocationEnum> = board.get_events().iter()
+            .filter_map(|event| match event {
+                GameEvent::RentPaid { location, .. } => Some(*location),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rent_paid, vec![LocationEnum::BalticAvenue, LocationEnum::OrientalAvenue]);
+    }
+}
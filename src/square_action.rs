@@ -0,0 +1,85 @@
+use std::rc::Rc;
+use crate::board_state::BoardState;
+use crate::dice::DiceRoll;
+use crate::events::GameEvent;
+use crate::location::LocationEnum;
+
+/// Who landed where, and with which roll.
+#[derive(Clone)]
+pub struct Landing {
+    pub player_number: usize,
+    pub location: LocationEnum,
+    pub roll: DiceRoll,
+}
+
+/// What happens when a player lands on a square. Actions get the whole board so they can reach
+/// owners, the bank, the decks and the other players.
+pub trait SquareAction {
+    fn on_land(&self, board: &mut BoardState, landing: &Landing);
+}
+
+impl<F: Fn(&mut BoardState, &Landing)> SquareAction for F {
+    fn on_land(&self, board: &mut BoardState, landing: &Landing) {
+        self(board, landing)
+    }
+}
+
+pub fn no_action(_: &mut BoardState, _: &Landing) {}
+
+/// Offers an unowned deed for sale, or charges rent to anyone but its owner.
+pub fn deed_action(board: &mut BoardState, landing: &Landing) {
+    match board.properties.get_property_ref(landing.location).owned_by_player_number {
+        None => board.offer_purchase(landing.player_number, landing.location),
+        Some(owner) if owner != landing.player_number => {
+            let rent = board.properties.get_rent(landing.location, landing.roll.total());
+            board.pay_rent(landing.player_number, owner, landing.location, rent);
+        },
+        Some(_) => {}
+    }
+}
+
+pub fn chance_action(board: &mut BoardState, landing: &Landing) {
+    board.draw_chance_card(&landing.roll);
+}
+
+pub fn community_chest_action(board: &mut BoardState, landing: &Landing) {
+    board.draw_community_chest_card(&landing.roll);
+}
+
+pub fn free_parking_action(board: &mut BoardState, landing: &Landing) {
+    board.collect_free_parking(landing.player_number);
+}
+
+pub fn go_to_jail_action(board: &mut BoardState, landing: &Landing) {
+    board.get_current_player_by_number(landing.player_number).send_to_jail();
+    board.emit(GameEvent::SentToJail { player_number: landing.player_number });
+}
+
+pub fn tax_action(amount: i32) -> Rc<dyn SquareAction> {
+    Rc::new(move |board: &mut BoardState, landing: &Landing| board.pay_fine(landing.player_number, amount))
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+    use crate::BoardState;
+    use crate::dice::DiceRoll;
+    use crate::location::LocationEnum;
+    use crate::square_action::Landing;
+    use crate::test_support::get_new_board;
+
+    #[test]
+    fn user_defined_square_can_reach_other_players() {
+        let mut board = get_new_board(DiceRoll(4, 5), 3);
+        board.properties.set_action(LocationEnum::ConnecticutAvenue, Rc::new(|board: &mut BoardState, landing: &Landing| {
+            for other in [2, 3] {
+                board.transfer_cash(other, landing.player_number, 25);
+            }
+        }));
+        board.take_turn();
+        assert_eq!(board.players[&1].current_location, LocationEnum::ConnecticutAvenue);
+        assert_eq!(board.players[&1].cash, 1550);
+        assert_eq!(board.players[&2].cash, 1475);
+        assert_eq!(board.properties.get_property(LocationEnum::ConnecticutAvenue).owned_by_player_number, None);
+    }
+}
@@ -0,0 +1,393 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::analysis::{JailPolicyEnum, LandingAnalysis};
+use crate::auction::MINIMUM_BID_INCREMENT;
+use crate::board_state::BoardState;
+use crate::controller::{PlayerController, TurnActionEnum};
+use crate::location::{ColorGroupEnum, LocationEnum, Property};
+use crate::simulation::Contestant;
+
+pub const DEFAULT_CASH_RESERVE: i32 = 100;
+pub const STRATEGY_NAMES: [&str; 5] = ["buy-everything", "never-buy", "orange-red", "three-houses", "rent-maximizer"];
+
+/// Buys every deed it lands on and bids up to the printed price in auctions, as long as
+/// `cash_reserve` is left over. Spare cash goes on houses, cheapest first, up to hotels.
+pub struct BuyEverything {
+    pub cash_reserve: i32,
+}
+
+impl BuyEverything {
+    pub fn new(cash_reserve: i32) -> Self {
+        Self { cash_reserve }
+    }
+}
+
+impl PlayerController for BuyEverything {
+    fn should_buy(&mut self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        can_afford(board, player_number, board.properties.get_property_ref(location).cost, self.cash_reserve)
+    }
+
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        bid_up_to(board, player_number, highest_bid, board.properties.get_property_ref(location).cost, self.cash_reserve)
+    }
+
+    fn plan_turn_actions(&mut self, board: &BoardState, player_number: usize) -> Vec<TurnActionEnum> {
+        plan_building(board, player_number, self.cash_reserve, 5, |property, _| -property.house_cost as f64)
+    }
+}
+
+/// Never buys or bids on anything, so it only ever pays rent. A floor for the other strategies.
+pub struct NeverBuy;
+
+impl PlayerController for NeverBuy {
+    fn should_buy(&mut self, _board: &BoardState, _player_number: usize, _location: LocationEnum) -> bool {
+        false
+    }
+
+    fn bid(&mut self, _board: &BoardState, _player_number: usize, _location: LocationEnum, _highest_bid: i32) -> Option<i32> {
+        None
+    }
+}
+
+/// Only buys streets in `groups`, paying up to `auction_premium` percent over the printed
+/// price for them at auction, and builds on them once a set is complete.
+pub struct SetCollector {
+    pub groups: Vec<ColorGroupEnum>,
+    pub cash_reserve: i32,
+    pub auction_premium: i32,
+}
+
+impl SetCollector {
+    pub fn new(groups: Vec<ColorGroupEnum>, cash_reserve: i32) -> Self {
+        Self { groups, cash_reserve, auction_premium: 20 }
+    }
+
+    /// The orange and red streets, which see the most traffic from Jail.
+    pub fn orange_red(cash_reserve: i32) -> Self {
+        SetCollector::new(vec![ColorGroupEnum::Orange, ColorGroupEnum::Red], cash_reserve)
+    }
+
+    fn wants(&self, location: LocationEnum) -> bool {
+        location.get_color_group().is_some_and(|group| self.groups.contains(&group))
+    }
+}
+
+impl PlayerController for SetCollector {
+    fn should_buy(&mut self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        self.wants(location) && can_afford(board, player_number, board.properties.get_property_ref(location).cost, self.cash_reserve)
+    }
+
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        if !self.wants(location) {
+            return None;
+        }
+        let limit = board.properties.get_property_ref(location).cost * (100 + self.auction_premium) / 100;
+        bid_up_to(board, player_number, highest_bid, limit, self.cash_reserve)
+    }
+
+    fn plan_turn_actions(&mut self, board: &BoardState, player_number: usize) -> Vec<TurnActionEnum> {
+        plan_building(board, player_number, self.cash_reserve, 5, |property, _| {
+            if self.wants(property.location) { 1.0 } else { 0.0 }
+        })
+    }
+}
+
+/// Buys like `BuyEverything`, but builds every set it completes up to three houses a street,
+/// the point where rent jumps the most, and stops there.
+pub struct ThreeHousesAsap {
+    pub cash_reserve: i32,
+}
+
+impl ThreeHousesAsap {
+    pub fn new(cash_reserve: i32) -> Self {
+        Self { cash_reserve }
+    }
+}
+
+impl PlayerController for ThreeHousesAsap {
+    fn should_buy(&mut self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        can_afford(board, player_number, board.properties.get_property_ref(location).cost, self.cash_reserve)
+    }
+
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        bid_up_to(board, player_number, highest_bid, board.properties.get_property_ref(location).cost, self.cash_reserve)
+    }
+
+    fn plan_turn_actions(&mut self, board: &BoardState, player_number: usize) -> Vec<TurnActionEnum> {
+        plan_building(board, player_number, self.cash_reserve, 3, |property, _| -property.house_cost as f64)
+    }
+}
+
+/// Weighs every decision by the rent it expects to collect per opponent turn, using
+/// `landing_odds` (the chance of an opponent ending a turn on each square, indexed from Go).
+/// It builds wherever the next house adds the most expected rent per dollar, and will pay
+/// past the printed price for a deed that completes a set for it or stops an opponent's.
+pub struct RentMaximizer {
+    pub cash_reserve: i32,
+    pub landing_odds: Vec<f64>,
+    pub set_premium: i32,
+}
+
+impl RentMaximizer {
+    /// Starts with every square equally likely.
+    pub fn new(cash_reserve: i32) -> Self {
+        Self { cash_reserve, landing_odds: vec![1.0 / 40.0; 40], set_premium: 50 }
+    }
+
+    /// Uses the long-run landing odds of opponents who sit out their jail time.
+    pub fn with_analysis(cash_reserve: i32, analysis: &LandingAnalysis) -> Self {
+        Self { landing_odds: analysis.landings_per_turn.clone(), ..RentMaximizer::new(cash_reserve) }
+    }
+
+    /// Rent expected per opponent turn from `property` at building `level`.
+    pub fn get_expected_rent(&self, property: &Property, level: i32) -> f64 {
+        let rent = property.rent.get(level as usize).copied().unwrap_or(0);
+        rent as f64 * self.landing_odds[property.location as usize - 1]
+    }
+
+    fn is_contested(&self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        let group = match location.get_color_group() {
+            Some(group) => group,
+            None => return false,
+        };
+        let owners: Vec<Option<usize>> = group.get_locations().into_iter()
+            .filter(|other| *other != location)
+            .map(|other| board.properties.get_property_ref(other).owned_by_player_number)
+            .collect();
+        // Either every other street is ours, or they all belong to one opponent.
+        owners.iter().all(|owner| *owner == Some(player_number))
+            || (owners[0].is_some() && owners.iter().all(|owner| *owner == owners[0]))
+    }
+}
+
+impl PlayerController for RentMaximizer {
+    fn should_buy(&mut self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        let cost = board.properties.get_property_ref(location).cost;
+        let reserve = if self.is_contested(board, player_number, location) { 0 } else { self.cash_reserve };
+        can_afford(board, player_number, cost, reserve)
+    }
+
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        let cost = board.properties.get_property_ref(location).cost;
+        let limit = if self.is_contested(board, player_number, location) { cost * (100 + self.set_premium) / 100 } else { cost };
+        bid_up_to(board, player_number, highest_bid, limit, self.cash_reserve)
+    }
+
+    fn plan_turn_actions(&mut self, board: &BoardState, player_number: usize) -> Vec<TurnActionEnum> {
+        plan_building(board, player_number, self.cash_reserve, 5, |property, level| {
+            // Unimproved streets in a complete set already charge double.
+            let current = self.get_expected_rent(property, level) * if level == 0 { 2.0 } else { 1.0 };
+            let gain = self.get_expected_rent(property, level + 1) - current;
+            gain / property.house_cost as f64
+        })
+    }
+}
+
+/// Looks up a built-in strategy by one of `STRATEGY_NAMES`.
+pub fn get_strategy(name: &str, cash_reserve: i32) -> Option<Contestant> {
+    if name == "rent-maximizer" {
+        // Worked out once here rather than for every game's controller.
+        let analysis = LandingAnalysis::new(JailPolicyEnum::StayInJail);
+        return Some(Contestant::new(name, move || Rc::new(RefCell::new(RentMaximizer::with_analysis(cash_reserve, &analysis)))));
+    }
+    let new_controller: fn(i32) -> Rc<RefCell<dyn PlayerController>> = match name {
+        "buy-everything" => |reserve| Rc::new(RefCell::new(BuyEverything::new(reserve))),
+        "never-buy" => |_| Rc::new(RefCell::new(NeverBuy)),
+        "orange-red" => |reserve| Rc::new(RefCell::new(SetCollector::orange_red(reserve))),
+        "three-houses" => |reserve| Rc::new(RefCell::new(ThreeHousesAsap::new(reserve))),
+        _ => return None,
+    };
+    Some(Contestant::new(name, move || new_controller(cash_reserve)))
+}
+
+fn can_afford(board: &BoardState, player_number: usize, cost: i32, cash_reserve: i32) -> bool {
+    board.players[&player_number].cash - cost >= cash_reserve
+}
+
+fn bid_up_to(board: &BoardState, player_number: usize, highest_bid: i32, limit: i32, cash_reserve: i32) -> Option<i32> {
+    let bid = highest_bid + MINIMUM_BID_INCREMENT;
+    if bid <= limit && can_afford(board, player_number, bid, cash_reserve) { Some(bid) } else { None }
+}
+
+/// Houses and hotels to buy with the cash above `cash_reserve`, building evenly up to
+/// `max_level` (5 being a hotel). Each step builds on the street `score` rates highest among
+/// those the even-building rule allows, given the street and its current level.
+fn plan_building(board: &BoardState,
+                 player_number: usize,
+                 cash_reserve: i32,
+                 max_level: i32,
+                 score: impl Fn(&Property, i32) -> f64) -> Vec<TurnActionEnum> {
+    let mut budget = board.players[&player_number].cash - cash_reserve;
+    let mut houses_available = board.houses_available;
+    let mut hotels_available = board.hotels_available;
+    let mut levels: Vec<(ColorGroupEnum, LocationEnum, i32)> = Vec::new();
+    for group in board.properties.get_monopolies(player_number) {
+        let locations = group.get_locations();
+        if locations.iter().any(|location| board.properties.get_property_ref(*location).is_mortgaged) {
+            continue;
+        }
+        for location in locations {
+            levels.push((group, location, board.properties.get_property_ref(location).building_level()));
+        }
+    }
+
+    let mut actions = Vec::new();
+    loop {
+        let mut best: Option<(usize, f64)> = None;
+        for (index, (group, location, level)) in levels.iter().enumerate() {
+            let lowest = levels.iter().filter(|(other, _, _)| other == group).map(|(_, _, level)| *level).min().unwrap_or(0);
+            let property = board.properties.get_property_ref(*location);
+            let in_stock = if *level == 4 { hotels_available > 0 } else { houses_available > 0 };
+            if *level != lowest || *level >= max_level || property.house_cost > budget || !in_stock {
+                continue;
+            }
+            let value = score(property, *level);
+            if best.map_or(true, |(_, best_value)| value > best_value) {
+                best = Some((index, value));
+            }
+        }
+
+        let index = match best {
+            Some((index, _)) => index,
+            None => break,
+        };
+        let (_, location, level) = &mut levels[index];
+        if *level == 4 {
+            actions.push(TurnActionEnum::BuyHotel(*location));
+            houses_available += 4;
+            hotels_available -= 1;
+        } else {
+            actions.push(TurnActionEnum::BuyHouse(*location));
+            houses_available -= 1;
+        }
+        *level += 1;
+        budget -= board.properties.get_property_ref(*location).house_cost;
+    }
+    actions
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::BoardState;
+    use crate::analysis::{JailPolicyEnum, LandingAnalysis};
+    use crate::controller::{PlayerController, TurnActionEnum};
+    use crate::dice::DiceRoll;
+    use crate::location::{ColorGroupEnum, LocationEnum};
+    use crate::simulation::{run_simulation, SimulationConfig};
+    use crate::strategy::{get_strategy, BuyEverything, NeverBuy, RentMaximizer, SetCollector, ThreeHousesAsap, STRATEGY_NAMES};
+    use crate::test_support::get_new_board;
+
+    fn give_group(board: &mut BoardState, player_number: usize, group: ColorGroupEnum) {
+        for location in group.get_locations() {
+            board.properties.get_property(location).owned_by_player_number = Some(player_number);
+        }
+    }
+
+    #[test]
+    fn buy_everything_keeps_its_reserve() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        let mut strategy = BuyEverything::new(250);
+        board.get_current_player_by_number(1).cash = 600;
+        assert!(strategy.should_buy(&board, 1, LocationEnum::BalticAvenue));
+        assert!(!strategy.should_buy(&board, 1, LocationEnum::Boardwalk));
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::Boardwalk, 330), Some(340));
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::Boardwalk, 350), None);
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::BalticAvenue, 60), None);
+    }
+
+    #[test]
+    fn never_buy_declines_and_lets_the_deed_go_to_auction() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.set_player_controller(1, Rc::new(RefCell::new(NeverBuy)));
+        board.take_turn();
+        assert_eq!(board.properties.get_property_ref(LocationEnum::BalticAvenue).owned_by_player_number, Some(2));
+    }
+
+    #[test]
+    fn set_collector_only_wants_its_groups() {
+        let board = get_new_board(DiceRoll(1, 2), 2);
+        let mut strategy = SetCollector::orange_red(0);
+        assert!(strategy.should_buy(&board, 1, LocationEnum::NewYorkAvenue));
+        assert!(strategy.should_buy(&board, 1, LocationEnum::IllinoisAvenue));
+        assert!(!strategy.should_buy(&board, 1, LocationEnum::Boardwalk));
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::StJamesPlace, 200), Some(210));
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::StJamesPlace, 216), None);
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::ReadingRailroad, 0), None);
+    }
+
+    #[test]
+    fn three_houses_builds_evenly_and_stops_at_three() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        give_group(&mut board, 1, ColorGroupEnum::LightBlue);
+        board.get_current_player_by_number(1).cash = 5000;
+        let actions = ThreeHousesAsap::new(0).plan_turn_actions(&board, 1);
+        assert_eq!(actions.len(), 9);
+        assert_eq!(&actions[..3], &[
+            TurnActionEnum::BuyHouse(LocationEnum::OrientalAvenue),
+            TurnActionEnum::BuyHouse(LocationEnum::VermontAvenue),
+            TurnActionEnum::BuyHouse(LocationEnum::ConnecticutAvenue),
+        ]);
+        for action in &actions {
+            board.perform_turn_action(1, action).unwrap();
+        }
+        for location in ColorGroupEnum::LightBlue.get_locations() {
+            assert_eq!(board.properties.get_property_ref(location).houses, 3);
+        }
+    }
+
+    #[test]
+    fn building_stops_at_the_cash_reserve() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        give_group(&mut board, 1, ColorGroupEnum::Brown);
+        board.get_current_player_by_number(1).cash = 260;
+        let actions = BuyEverything::new(100).plan_turn_actions(&board, 1);
+        assert_eq!(actions, vec![
+            TurnActionEnum::BuyHouse(LocationEnum::MediterraneanAvenue),
+            TurnActionEnum::BuyHouse(LocationEnum::BalticAvenue),
+            TurnActionEnum::BuyHouse(LocationEnum::MediterraneanAvenue),
+        ]);
+    }
+
+    #[test]
+    fn rent_maximizer_builds_where_the_odds_are_best() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        give_group(&mut board, 1, ColorGroupEnum::Brown);
+        give_group(&mut board, 1, ColorGroupEnum::DarkBlue);
+        board.get_current_player_by_number(1).cash = 200;
+        let mut strategy = RentMaximizer::new(0);
+        assert_eq!(strategy.plan_turn_actions(&board, 1), vec![TurnActionEnum::BuyHouse(LocationEnum::ParkPlace)]);
+
+        let analysis = LandingAnalysis::new(JailPolicyEnum::StayInJail);
+        let mut informed = RentMaximizer::with_analysis(0, &analysis);
+        assert_eq!(informed.plan_turn_actions(&board, 1), vec![TurnActionEnum::BuyHouse(LocationEnum::Boardwalk)]);
+
+        strategy.landing_odds[LocationEnum::Boardwalk as usize - 1] = 0.0;
+        strategy.landing_odds[LocationEnum::ParkPlace as usize - 1] = 0.0;
+        assert_eq!(strategy.plan_turn_actions(&board, 1)[0], TurnActionEnum::BuyHouse(LocationEnum::BalticAvenue));
+    }
+
+    #[test]
+    fn rent_maximizer_stretches_for_a_set() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.properties.get_property(LocationEnum::ParkPlace).owned_by_player_number = Some(2);
+        board.get_current_player_by_number(1).cash = 1000;
+        let mut strategy = RentMaximizer::new(200);
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::Boardwalk, 450), Some(460));
+        assert_eq!(strategy.bid(&board, 1, LocationEnum::PacificAvenue, 300), None);
+        board.get_current_player_by_number(1).cash = 450;
+        assert!(strategy.should_buy(&board, 1, LocationEnum::Boardwalk));
+        assert!(!strategy.should_buy(&board, 1, LocationEnum::PacificAvenue));
+    }
+
+    #[test]
+    fn every_named_strategy_plays_a_simulation() {
+        let contestants = STRATEGY_NAMES.iter().map(|name| get_strategy(name, 150).unwrap()).collect();
+        let mut config = SimulationConfig::new(contestants, 4, 1);
+        config.max_turns = 400;
+        let report = run_simulation(&config);
+        assert_eq!(report.contestants.len(), 5);
+        assert!(get_strategy("random", 0).is_none());
+    }
+}
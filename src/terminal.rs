@@ -0,0 +1,438 @@
+use std::cell::RefCell;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+use crate::board_state::BoardState;
+use crate::controller::{PlayerController, TurnActionEnum};
+use crate::dice::Dice;
+use crate::events::{EventSubscriber, GameEvent};
+use crate::jail::JailOptionEnum;
+use crate::location::{LocationEnum, BOARD_SIZE};
+use crate::render::{render_board, BorderStyleEnum};
+use crate::rules::GameRules;
+use crate::trade::{TradeOffer, TradeSide};
+use crate::Player;
+
+/// Line-based prompts over any input and output, so games can be scripted in tests.
+pub struct Terminal {
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+}
+
+impl Terminal {
+    pub fn new(input: Box<dyn BufRead>, output: Box<dyn Write>) -> Self {
+        Self { input, output }
+    }
+
+    pub fn stdio() -> Self {
+        Terminal::new(Box::new(io::BufReader::new(io::stdin())), Box::new(io::stdout()))
+    }
+
+    pub fn say(&mut self, line: &str) {
+        let _ = writeln!(self.output, "{}", line);
+    }
+
+    /// Prints `question` and reads one trimmed line, or None once the input has ended.
+    pub fn ask(&mut self, question: &str) -> Option<String> {
+        let _ = write!(self.output, "{} ", question);
+        let _ = self.output.flush();
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().to_string()),
+        }
+    }
+
+    pub fn ask_yes_no(&mut self, question: &str) -> Option<bool> {
+        loop {
+            match self.ask(&format!("{} [y/n]", question))?.to_lowercase().as_str() {
+                "y" | "yes" => return Some(true),
+                "n" | "no" => return Some(false),
+                _ => self.say("Please answer y or n."),
+            }
+        }
+    }
+
+    /// Reads a whole number, with a blank answer giving None.
+    pub fn ask_number(&mut self, question: &str) -> Option<Option<i32>> {
+        loop {
+            let answer = self.ask(question)?;
+            if answer.is_empty() {
+                return Some(None);
+            }
+            match answer.parse() {
+                Ok(number) => return Some(Some(number)),
+                Err(_) => self.say("Please enter a number."),
+            }
+        }
+    }
+
+    /// Lists `options` from 1 and returns the index picked, or None for a blank answer.
+    pub fn choose(&mut self, question: &str, options: &[String]) -> Option<Option<usize>> {
+        for (index, option) in options.iter().enumerate() {
+            self.say(&format!("  {}) {}", index + 1, option));
+        }
+        loop {
+            match self.ask_number(question)? {
+                None => return Some(None),
+                Some(number) if number >= 1 && number as usize <= options.len() => return Some(Some(number as usize - 1)),
+                Some(_) => self.say("That is not one of the options."),
+            }
+        }
+    }
+
+    /// Picks any number of `options` from a comma separated list of their numbers.
+    pub fn choose_many(&mut self, question: &str, options: &[String]) -> Option<Vec<usize>> {
+        for (index, option) in options.iter().enumerate() {
+            self.say(&format!("  {}) {}", index + 1, option));
+        }
+        'ask: loop {
+            let answer = self.ask(question)?;
+            let mut picked = Vec::new();
+            for part in answer.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+                match part.parse::<usize>() {
+                    Ok(number) if number >= 1 && number <= options.len() => picked.push(number - 1),
+                    _ => {
+                        self.say("Enter option numbers separated by commas.");
+                        continue 'ask;
+                    },
+                }
+            }
+            return Some(picked);
+        }
+    }
+}
+
+/// Asks the human at the keyboard for every decision. Once input runs out it answers as the
+/// default controller would, so a closed terminal never stalls the game.
+pub struct TerminalController(pub Rc<RefCell<Terminal>>);
+
+impl PlayerController for TerminalController {
+    fn should_buy(&mut self, board: &BoardState, player_number: usize, location: LocationEnum) -> bool {
+        let player = &board.players[&player_number];
+        let cost = board.properties.get_property_ref(location).cost;
+        let question = format!("{}, buy {} for ${}? You have ${}.", player.name, location.get_name(), cost, player.cash);
+        self.0.borrow_mut().ask_yes_no(&question).unwrap_or(false)
+    }
+
+    fn bid(&mut self, board: &BoardState, player_number: usize, location: LocationEnum, highest_bid: i32) -> Option<i32> {
+        let player = &board.players[&player_number];
+        let question = format!("{}, bid for {} (highest ${}, you have ${}, blank to pass):",
+            player.name, location.get_name(), highest_bid, player.cash);
+        self.0.borrow_mut().ask_number(&question).flatten()
+    }
+
+    fn choose_jail_option(&mut self, board: &BoardState, player_number: usize, options: &[JailOptionEnum]) -> JailOptionEnum {
+        let names: Vec<String> = options.iter().map(|option| match option {
+            JailOptionEnum::PayFine => format!("Pay the ${} fine", board.rules.jail_fine),
+            JailOptionEnum::UseGetOutOfJailFreeCard => "Use a Get Out of Jail Free card".to_string(),
+            JailOptionEnum::RollForDoubles => "Roll for doubles".to_string(),
+        }).collect();
+        let question = format!("{}, you are in jail. Choose:", board.players[&player_number].name);
+        match self.0.borrow_mut().choose(&question, &names).flatten() {
+            Some(index) => options[index],
+            None => JailOptionEnum::RollForDoubles,
+        }
+    }
+
+    fn choose_destination(&mut self, board: &BoardState, player_number: usize) -> LocationEnum {
+        let player = &board.players[&player_number];
+        let squares: Vec<LocationEnum> = (1..=BOARD_SIZE as usize)
+            .map(|square| LocationEnum::try_from(square).expect("Every square number is on the board!"))
+            .collect();
+        let names: Vec<String> = squares.iter().map(|square| square.get_name().to_string()).collect();
+        let question = format!("{}, you rolled triples! Move to:", player.name);
+        match self.0.borrow_mut().choose(&question, &names).flatten() {
+            Some(index) => squares[index],
+            None => player.current_location,
+        }
+    }
+
+    fn choose_bus_move(&mut self, board: &BoardState, player_number: usize, options: &[i32]) -> i32 {
+        let names: Vec<String> = options.iter().map(|spaces| format!("Move {} spaces", spaces)).collect();
+        let question = format!("{}, you rolled the Bus. Choose:", board.players[&player_number].name);
+        match self.0.borrow_mut().choose(&question, &names).flatten() {
+            Some(index) => options[index],
+            None => options[options.len() - 1],
+        }
+    }
+
+    fn respond_to_trade(&mut self, board: &BoardState, player_number: usize, offer: &TradeOffer) -> bool {
+        let mut terminal = self.0.borrow_mut();
+        terminal.say(&format!("{} offers {}: {}", board.players[&offer.from_player_number].name,
+            board.players[&player_number].name, describe_offer(offer)));
+        terminal.ask_yes_no("Accept the trade?").unwrap_or(false)
+    }
+}
+
+/// Prints each event as it happens, so prompts appear in the order things occur.
+struct EventPrinter {
+    terminal: Rc<RefCell<Terminal>>,
+    names: Vec<String>,
+}
+
+impl EventSubscriber for EventPrinter {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.terminal.borrow_mut().say(&describe_event(&self.names, event));
+    }
+}
+
+/// Prompts for the players, then plays until someone wins. Returns the winner, or None if
+/// the input ended first.
+pub fn run_game(terminal: Rc<RefCell<Terminal>>, rules: GameRules) -> Option<usize> {
+    let mut board = set_up_game(&terminal, rules)?;
+    let names = (1..=board.players.len()).map(|player_number| board.players[&player_number].name.clone()).collect();
+    board.subscribe(Rc::new(RefCell::new(EventPrinter { terminal: terminal.clone(), names })));
+    while !board.is_game_over() {
+        let player_number = board.player_turn;
+        terminal.borrow_mut().say(&render_board(&board, BorderStyleEnum::Unicode));
+        terminal.borrow_mut().say(&describe_board(&board));
+        manage_properties(&terminal, &mut board, player_number)?;
+        terminal.borrow_mut().ask(&format!("{}, press enter to roll.", board.players[&player_number].name))?;
+
+        board.take_turn();
+    }
+
+    let winner = board.get_winner()?;
+    terminal.borrow_mut().say(&format!("{} wins!", board.players[&winner].name));
+    Some(winner)
+}
+
+fn set_up_game(terminal: &Rc<RefCell<Terminal>>, rules: GameRules) -> Option<BoardState> {
+    let mut term = terminal.borrow_mut();
+    let player_count = loop {
+        match term.ask_number("How many players (2-8)?")? {
+            Some(count) if (2..=8).contains(&count) => break count,
+            _ => term.say("Monopoly is played by 2 to 8 players."),
+        }
+    };
+    drop(term);
+
+    let mut board = BoardState::new(Rc::new(RefCell::new(Dice{})), rules);
+    board.seat_new_players(player_count);
+    for player_number in 1..=player_count as usize {
+        let name = terminal.borrow_mut().ask(&format!("Name for player {}:", player_number))?;
+        let player = board.get_current_player_by_number(player_number);
+        if !name.is_empty() {
+            player.name = name;
+        }
+        board.set_player_controller(player_number, Rc::new(RefCell::new(TerminalController(terminal.clone()))));
+    }
+    Some(board)
+}
+
+// Building, mortgaging and trading happen before the roll, one action at a time, so each
+// result is shown straight away.
+fn manage_properties(terminal: &Rc<RefCell<Terminal>>, board: &mut BoardState, player_number: usize) -> Option<()> {
+    let menu: Vec<String> = ["Buy a house", "Buy a hotel", "Sell a house", "Sell a hotel", "Mortgage", "Unmortgage", "Propose a trade"]
+        .iter().map(|item| item.to_string()).collect();
+    loop {
+        let question = format!("{}, manage properties (blank to continue):", board.players[&player_number].name);
+        let choice = match terminal.borrow_mut().choose(&question, &menu)? {
+            Some(choice) => choice,
+            None => return Some(()),
+        };
+
+        let action = if choice == 6 {
+            match propose_trade(terminal, board, player_number)? {
+                Some(offer) => TurnActionEnum::ProposeTrade(offer),
+                None => continue,
+            }
+        } else {
+            let deeds = board.get_owned_deeds(player_number);
+            let names: Vec<String> = deeds.iter().map(|location| location.get_name().to_string()).collect();
+            let location = match terminal.borrow_mut().choose("Which property?", &names)? {
+                Some(index) => deeds[index],
+                None => continue,
+            };
+            match choice {
+                0 => TurnActionEnum::BuyHouse(location),
+                1 => TurnActionEnum::BuyHotel(location),
+                2 => TurnActionEnum::SellHouse(location),
+                3 => TurnActionEnum::SellHotel(location),
+                4 => TurnActionEnum::Mortgage(location),
+                _ => TurnActionEnum::Unmortgage(location),
+            }
+        };
+
+        match board.perform_turn_action(player_number, &action) {
+            Ok(()) => terminal.borrow_mut().say("Done."),
+            Err(error) => terminal.borrow_mut().say(&format!("Could not do that: {}.", error)),
+        }
+    }
+}
+
+fn propose_trade(terminal: &Rc<RefCell<Terminal>>, board: &BoardState, player_number: usize) -> Option<Option<TradeOffer>> {
+    let others: Vec<usize> = (1..=board.players.len())
+        .filter(|other| *other != player_number && !board.players[other].is_bankrupt)
+        .collect();
+    let names: Vec<String> = others.iter().map(|other| board.players[other].name.clone()).collect();
+    let mut term = terminal.borrow_mut();
+    let to = match term.choose("Trade with whom?", &names)? {
+        Some(index) => others[index],
+        None => return Some(None),
+    };
+
+    let offered = ask_trade_side(&mut term, board, player_number, "you give")?;
+    let requested = ask_trade_side(&mut term, board, to, "you get")?;
+    Some(Some(TradeOffer::new(player_number, to, offered, requested)))
+}
+
+fn ask_trade_side(terminal: &mut Terminal, board: &BoardState, player_number: usize, label: &str) -> Option<TradeSide> {
+    let deeds = board.get_owned_deeds(player_number);
+    let names: Vec<String> = deeds.iter().map(|location| location.get_name().to_string()).collect();
+    let picked = terminal.choose_many(&format!("Properties {} (numbers, comma separated):", label), &names)?;
+    let cash = terminal.ask_number(&format!("Cash {} (blank for none):", label))?.unwrap_or(0);
+    Some(TradeSide {
+        properties: picked.into_iter().map(|index| deeds[index]).collect(),
+        cash,
+        ..Default::default()
+    })
+}
+
+fn describe_offer(offer: &TradeOffer) -> String {
+    let describe_side = |side: &TradeSide| {
+        let mut parts: Vec<String> = side.properties.iter().map(|location| location.get_name().to_string()).collect();
+        if side.cash > 0 {
+            parts.push(format!("${}", side.cash));
+        }
+        parts.extend(side.get_out_of_jail_free_cards.iter().map(|_| "a Get Out of Jail Free card".to_string()));
+        if parts.is_empty() { "nothing".to_string() } else { parts.join(", ") }
+    };
+    format!("{} for {}", describe_side(&offer.offered), describe_side(&offer.requested))
+}
+
+/// One line per player with their cash, square and deeds.
+pub fn describe_board(board: &BoardState) -> String {
+    let mut lines = Vec::new();
+    for player_number in 1..=board.players.len() {
+        let player: &Player = &board.players[&player_number];
+        if player.is_bankrupt {
+            lines.push(format!("{}: bankrupt", player.name));
+            continue;
+        }
+
+        let deeds: Vec<String> = board.get_owned_deeds(player_number).into_iter()
+            .map(|location| {
+                let property = board.properties.get_property_ref(location);
+                match (property.is_mortgaged, property.hotels, property.houses) {
+                    (true, _, _) => format!("{} (mortgaged)", location.get_name()),
+                    (_, 1.., _) => format!("{} (hotel)", location.get_name()),
+                    (_, _, 1..) => format!("{} ({} houses)", location.get_name(), property.houses),
+                    _ => location.get_name().to_string(),
+                }
+            })
+            .collect();
+        let jail = if player.is_in_jail { " in jail" } else { "" };
+        lines.push(format!("{}: ${} on {}{}. Owns: {}", player.name, player.cash, player.current_location.get_name(), jail,
+            if deeds.is_empty() { "nothing".to_string() } else { deeds.join(", ") }));
+    }
+    lines.join("\n")
+}
+
+/// `names` holds each player's name, starting with player 1.
+pub fn describe_event(names: &[String], event: &GameEvent) -> String {
+    let name = |player_number: &usize| names[player_number - 1].clone();
+    match event {
+        GameEvent::TurnStarted { player_number } => format!("-- {}'s turn --", name(player_number)),
+        GameEvent::Rolled { player_number, roll } => format!("{} rolled {} and {}.", name(player_number), roll.0, roll.1),
+        GameEvent::SpeedDieRolled { player_number, face } => format!("{} rolled {} on the speed die.", name(player_number), face.get_name()),
+        GameEvent::Moved { player_number, to, .. } => format!("{} moved to {}.", name(player_number), to.get_name()),
+        GameEvent::PassedGo { player_number } => format!("{} passed Go.", name(player_number)),
+        GameEvent::PropertyPurchased { player_number, location, price } => format!("{} bought {} for ${}.", name(player_number), location.get_name(), price),
+        GameEvent::AuctionWon { player_number, location, price } => format!("{} won {} at auction for ${}.", name(player_number), location.get_name(), price),
+        GameEvent::RentPaid { player_number, owner, amount, .. } => format!("{} paid ${} rent to {}.", name(player_number), amount, name(owner)),
+        GameEvent::ChanceCardDrawn { player_number, card } => format!("{} drew Chance: {:?}.", name(player_number), card),
+        GameEvent::CommunityChestCardDrawn { player_number, card } => format!("{} drew Community Chest: {:?}.", name(player_number), card),
+        GameEvent::SentToJail { player_number } => format!("{} went to jail.", name(player_number)),
+        GameEvent::LeftJail { player_number, .. } => format!("{} got out of jail.", name(player_number)),
+        GameEvent::HouseBuilt { player_number, location } => format!("{} built a house on {}.", name(player_number), location.get_name()),
+        GameEvent::HotelBuilt { player_number, location } => format!("{} built a hotel on {}.", name(player_number), location.get_name()),
+        GameEvent::HouseSold { player_number, location } => format!("{} sold a house on {}.", name(player_number), location.get_name()),
+        GameEvent::HotelSold { player_number, location } => format!("{} sold a hotel on {}.", name(player_number), location.get_name()),
+        GameEvent::Mortgaged { player_number, location } => format!("{} mortgaged {}.", name(player_number), location.get_name()),
+        GameEvent::Unmortgaged { player_number, location } => format!("{} lifted the mortgage on {}.", name(player_number), location.get_name()),
+        GameEvent::TradeCompleted(offer) => format!("{} and {} traded {}.", name(&offer.from_player_number), name(&offer.to_player_number), describe_offer(offer)),
+        GameEvent::Bankrupt { player_number, .. } => format!("{} is bankrupt!", name(player_number)),
+        GameEvent::FreeParkingCollected { player_number, amount } => format!("{} collected ${} from Free Parking.", name(player_number), amount),
+        GameEvent::ActionRejected { player_number, error } => format!("{} could not do that: {}.", name(player_number), error),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::io::{Cursor, Write};
+    use std::rc::Rc;
+    use crate::controller::PlayerController;
+    use crate::dice::DiceRoll;
+    use crate::jail::JailOptionEnum;
+    use crate::location::LocationEnum;
+    use crate::rules::GameRules;
+    use crate::terminal::{run_game, Terminal, TerminalController};
+    use crate::test_support::{get_board_with_rules, get_new_board};
+    use crate::trade::{TradeOffer, TradeSide};
+
+    /// Output shared with the test after the terminal takes ownership of it.
+    #[derive(Clone)]
+    struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn get_terminal(input: &str) -> (Rc<RefCell<Terminal>>, SharedOutput) {
+        let output = SharedOutput(Rc::new(RefCell::new(Vec::new())));
+        let terminal = Terminal::new(Box::new(Cursor::new(input.to_string())), Box::new(output.clone()));
+        (Rc::new(RefCell::new(terminal)), output)
+    }
+
+    #[test]
+    fn controller_asks_before_buying_and_bidding() {
+        let (terminal, _) = get_terminal("maybe\ny\n\n120\n");
+        let board = get_new_board(DiceRoll(1, 2), 2);
+        let mut controller = TerminalController(terminal);
+        assert!(controller.should_buy(&board, 1, LocationEnum::Boardwalk));
+        assert_eq!(controller.bid(&board, 1, LocationEnum::Boardwalk, 100), None);
+        assert_eq!(controller.bid(&board, 1, LocationEnum::Boardwalk, 100), Some(120));
+        assert_eq!(controller.bid(&board, 1, LocationEnum::Boardwalk, 130), None);
+    }
+
+    #[test]
+    fn trade_offers_are_shown_to_the_other_player() {
+        let (terminal, output) = get_terminal("y\n");
+        let board = get_new_board(DiceRoll(1, 2), 2);
+        let offer = TradeOffer::new(1, 2,
+            TradeSide { cash: 50, ..Default::default() },
+            TradeSide { properties: vec![LocationEnum::Boardwalk], ..Default::default() });
+        assert!(TerminalController(terminal).respond_to_trade(&board, 2, &offer));
+        let printed = String::from_utf8(output.0.borrow().clone()).unwrap();
+        assert!(printed.contains("Player 1 offers Player 2: $50 for Boardwalk"));
+    }
+
+    #[test]
+    fn jail_prompt_quotes_the_rules_fine() {
+        let (terminal, output) = get_terminal("1\n");
+        let rules = GameRules { jail_fine: 75, ..GameRules::default() };
+        let board = get_board_with_rules(DiceRoll(1, 2), 2, rules);
+        let options = [JailOptionEnum::PayFine, JailOptionEnum::RollForDoubles];
+        assert_eq!(TerminalController(terminal).choose_jail_option(&board, 1, &options), JailOptionEnum::PayFine);
+        let printed = String::from_utf8(output.0.borrow().clone()).unwrap();
+        assert!(printed.contains("Pay the $75 fine"));
+    }
+
+    #[test]
+    fn game_sets_up_players_and_stops_when_input_ends() {
+        let (terminal, output) = get_terminal("1\n2\nAda\n\n\n\n");
+        assert_eq!(run_game(terminal, GameRules::default()), None);
+        let printed = String::from_utf8(output.0.borrow().clone()).unwrap();
+        assert!(printed.contains("Monopoly is played by 2 to 8 players."));
+        assert!(printed.contains("Ada: $1500 on Go. Owns: nothing"));
+        assert!(printed.contains("Player 2: $1500 on Go"));
+        assert!(printed.contains("-- Ada's turn --"));
+    }
+}
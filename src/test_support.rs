@@ -0,0 +1,36 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::BoardState;
+use crate::dice::{DiceRoll, RollEngine, SpeedDieFace};
+use crate::rules::GameRules;
+
+/// Dice that always show the same roll.
+pub(crate) struct FixedRollEngine(pub DiceRoll);
+impl RollEngine for FixedRollEngine {
+    fn roll(&mut self) -> DiceRoll {
+        self.0.clone()
+    }
+}
+
+/// Fixed dice along with a speed die that always shows the same face.
+pub(crate) struct FixedSpeedDice(pub DiceRoll, pub SpeedDieFace);
+impl RollEngine for FixedSpeedDice {
+    fn roll(&mut self) -> DiceRoll {
+        self.0.clone()
+    }
+
+    fn roll_speed_die(&mut self) -> Option<SpeedDieFace> {
+        Some(self.1)
+    }
+}
+
+/// A standard game of `player_count` players whose dice always show `roll`.
+pub(crate) fn get_new_board(roll: DiceRoll, player_count: i32) -> BoardState {
+    get_board_with_rules(roll, player_count, GameRules::default())
+}
+
+pub(crate) fn get_board_with_rules(roll: DiceRoll, player_count: i32, rules: GameRules) -> BoardState {
+    let mut board = BoardState::new(Rc::new(RefCell::new(FixedRollEngine(roll))), rules);
+    board.seat_new_players(player_count);
+    board
+}
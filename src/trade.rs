@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+use crate::board_state::BoardState;
+use crate::cards::DeckEnum;
+use crate::error::GameError;
+use crate::events::GameEvent;
+use crate::location::LocationEnum;
+
+/// Everything one player hands over in a trade.
+#[derive(PartialEq, Eq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TradeSide {
+    pub properties: Vec<LocationEnum>,
+    pub cash: i32,
+    pub get_out_of_jail_free_cards: Vec<DeckEnum>,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+pub struct TradeOffer {
+    pub from_player_number: usize,
+    pub to_player_number: usize,
+    /// What `from_player_number` gives up.
+    pub offered: TradeSide,
+    /// What `from_player_number` asks for in return.
+    pub requested: TradeSide,
+    /// Mortgaged deeds the receiving player repays immediately instead of only paying interest.
+    pub lift_mortgages: Vec<LocationEnum>,
+}
+
+impl TradeOffer {
+    pub fn new(from_player_number: usize, to_player_number: usize, offered: TradeSide, requested: TradeSide) -> Self {
+        Self {
+            from_player_number,
+            to_player_number,
+            offered,
+            requested,
+            lift_mortgages: Vec::new(),
+        }
+    }
+}
+
+impl BoardState {
+    /// Checks both players own what they are trading, can pay what they owe, and that no traded
+    /// street belongs to a color group with buildings.
+    pub fn validate_trade(&self, offer: &TradeOffer) -> Result<(), GameError> {
+        let (from, to) = (offer.from_player_number, offer.to_player_number);
+        if from == to || !self.players.contains_key(&from) || !self.players.contains_key(&to)
+            || self.players[&from].is_bankrupt || self.players[&to].is_bankrupt {
+            return Err(GameError::InvalidTrade);
+        }
+
+        self.validate_trade_side(from, &offer.offered)?;
+        self.validate_trade_side(to, &offer.requested)?;
+
+        let from_cash = self.players[&from].cash - offer.offered.cash + offer.requested.cash
+            - self.get_mortgage_transfer_cost(&offer.requested.properties, &offer.lift_mortgages);
+        let to_cash = self.players[&to].cash - offer.requested.cash + offer.offered.cash
+            - self.get_mortgage_transfer_cost(&offer.offered.properties, &offer.lift_mortgages);
+        for (player_number, cash) in [(from, from_cash), (to, to_cash)] {
+            if cash < 0 {
+                let available = self.players[&player_number].cash;
+                return Err(GameError::InsufficientFunds { needed: available - cash, available });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates then carries out the whole trade, so either everything changes hands or nothing does.
+    pub fn accept_trade(&mut self, offer: &TradeOffer) -> Result<(), GameError> {
+        self.validate_trade(offer)?;
+
+        let (from, to) = (offer.from_player_number, offer.to_player_number);
+        for (giver, receiver, side) in [(from, to, &offer.offered), (to, from, &offer.requested)] {
+            self.get_current_player_by_number(giver).cash -= side.cash;
+            self.get_current_player_by_number(receiver).cash += side.cash;
+            for deck in &side.get_out_of_jail_free_cards {
+                let cards = &mut self.get_current_player_by_number(giver).get_out_of_jail_free_cards;
+                let index = cards.iter().position(|card| card == deck).ok_or(GameError::NoGetOutOfJailFreeCard)?;
+                cards.remove(index);
+                self.get_current_player_by_number(receiver).get_out_of_jail_free_cards.push(*deck);
+            }
+        }
+        for (receiver, side) in [(to, &offer.offered), (from, &offer.requested)] {
+            for location in &side.properties {
+                self.transfer_property(*location, receiver, offer.lift_mortgages.contains(location))?;
+            }
+        }
+
+        self.emit(GameEvent::TradeCompleted(offer.clone()));
+        Ok(())
+    }
+
+    fn validate_trade_side(&self, player_number: usize, side: &TradeSide) -> Result<(), GameError> {
+        if side.cash < 0 {
+            return Err(GameError::InvalidTrade);
+        }
+        let player = &self.players[&player_number];
+        if player.cash < side.cash {
+            return Err(GameError::InsufficientFunds { needed: side.cash, available: player.cash });
+        }
+
+        for location in &side.properties {
+            let property = self.properties.get_property_ref(*location);
+            if property.owned_by_player_number != Some(player_number) {
+                return Err(GameError::NotOwner(*location));
+            }
+            if let Some(group) = property.get_color_group() {
+                if self.highest_building_level(group) > 0 {
+                    return Err(GameError::GroupHasBuildings(group));
+                }
+            }
+        }
+
+        for deck in [DeckEnum::Chance, DeckEnum::CommunityChest] {
+            let offered = side.get_out_of_jail_free_cards.iter().filter(|card| **card == deck).count();
+            let held = player.get_out_of_jail_free_cards.iter().filter(|card| **card == deck).count();
+            if offered > held {
+                return Err(GameError::NoGetOutOfJailFreeCard);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_mortgage_transfer_cost(&self, locations: &[LocationEnum], lift_mortgages: &[LocationEnum]) -> i32 {
+        locations.iter()
+            .map(|location| self.properties.get_property_ref(*location))
+            .filter(|property| property.is_mortgaged)
+            .map(|property| if lift_mortgages.contains(&property.location) {
+                property.mortgage_value + property.mortgage_interest()
+            } else {
+                property.mortgage_interest()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cards::DeckEnum;
+    use crate::dice::DiceRoll;
+    use crate::error::GameError;
+    use crate::location::{ColorGroupEnum, LocationEnum};
+    use crate::trade::{TradeOffer, TradeSide};
+    use crate::test_support::get_new_board;
+
+    #[test]
+    fn trade_swaps_properties_cash_and_cards() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::ParkPlace).unwrap();
+        board.buy_property(2, LocationEnum::Boardwalk).unwrap();
+        board.get_current_player_by_number(2).get_out_of_jail_free_cards.push(DeckEnum::CommunityChest);
+
+        let offer = TradeOffer::new(1, 2,
+            TradeSide { properties: vec![LocationEnum::ParkPlace], cash: 100, ..Default::default() },
+            TradeSide { properties: vec![LocationEnum::Boardwalk], get_out_of_jail_free_cards: vec![DeckEnum::CommunityChest], ..Default::default() });
+        board.accept_trade(&offer).unwrap();
+
+        assert_eq!(board.properties.get_property(LocationEnum::ParkPlace).owned_by_player_number, Some(2));
+        assert_eq!(board.properties.get_property(LocationEnum::Boardwalk).owned_by_player_number, Some(1));
+        assert_eq!(board.players[&1].cash, 1500 - 350 - 100);
+        assert_eq!(board.players[&2].cash, 1500 - 400 + 100);
+        assert_eq!(board.players[&1].get_out_of_jail_free_cards, vec![DeckEnum::CommunityChest]);
+        assert!(board.players[&2].get_out_of_jail_free_cards.is_empty());
+    }
+
+    #[test]
+    fn cannot_trade_what_you_do_not_own() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(2, LocationEnum::Boardwalk).unwrap();
+        let offer = TradeOffer::new(1, 2,
+            TradeSide { properties: vec![LocationEnum::Boardwalk], ..Default::default() },
+            TradeSide { cash: 10, ..Default::default() });
+        assert_eq!(board.accept_trade(&offer), Err(GameError::NotOwner(LocationEnum::Boardwalk)));
+
+        let offer = TradeOffer::new(1, 2,
+            TradeSide { get_out_of_jail_free_cards: vec![DeckEnum::Chance], ..Default::default() },
+            TradeSide::default());
+        assert_eq!(board.accept_trade(&offer), Err(GameError::NoGetOutOfJailFreeCard));
+    }
+
+    #[test]
+    fn cannot_trade_streets_from_a_group_with_buildings() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        for location in ColorGroupEnum::DarkBlue.get_locations() {
+            board.buy_property(1, location).unwrap();
+        }
+        board.buy_house(1, LocationEnum::Boardwalk).unwrap();
+        let offer = TradeOffer::new(1, 2,
+            TradeSide { properties: vec![LocationEnum::ParkPlace], ..Default::default() },
+            TradeSide::default());
+        assert_eq!(board.accept_trade(&offer), Err(GameError::GroupHasBuildings(ColorGroupEnum::DarkBlue)));
+        assert_eq!(board.properties.get_property(LocationEnum::ParkPlace).owned_by_player_number, Some(1));
+    }
+
+    #[test]
+    fn failed_trade_changes_nothing() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::ParkPlace).unwrap();
+        let offer = TradeOffer::new(1, 2,
+            TradeSide { properties: vec![LocationEnum::ParkPlace], ..Default::default() },
+            TradeSide { cash: 5000, ..Default::default() });
+        assert_eq!(board.accept_trade(&offer), Err(GameError::InsufficientFunds { needed: 5000, available: 1500 }));
+        assert_eq!(board.properties.get_property(LocationEnum::ParkPlace).owned_by_player_number, Some(1));
+        assert_eq!(board.players[&1].cash, 1150);
+    }
+
+    #[test]
+    fn receiving_mortgaged_deeds_costs_interest_or_the_full_lift() {
+        let mut board = get_new_board(DiceRoll(1, 2), 2);
+        board.buy_property(1, LocationEnum::ParkPlace).unwrap();
+        board.buy_property(1, LocationEnum::Boardwalk).unwrap();
+        board.mortgage_property(1, LocationEnum::ParkPlace).unwrap();
+        board.mortgage_property(1, LocationEnum::Boardwalk).unwrap();
+
+        let mut offer = TradeOffer::new(1, 2,
+            TradeSide { properties: vec![LocationEnum::ParkPlace, LocationEnum::Boardwalk], ..Default::default() },
+            TradeSide::default());
+        offer.lift_mortgages = vec![LocationEnum::Boardwalk];
+        board.accept_trade(&offer).unwrap();
+
+        assert_eq!(board.players[&2].cash, 1500 - 18 - 220);
+        assert!(board.properties.get_property(LocationEnum::ParkPlace).is_mortgaged);
+        assert!(!board.properties.get_property(LocationEnum::Boardwalk).is_mortgaged);
+    }
+}